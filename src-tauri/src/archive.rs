@@ -0,0 +1,400 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::clients::{self, Client, SkillType};
+use crate::hashing;
+use crate::installer::{self, InstallMode, InstallTarget, SkillFile};
+use crate::lockfile::LockSource;
+use crate::remote::SKILL_FILE;
+use crate::skill_md;
+
+// Upper bound on the uncompressed size of everything read from one source, so a
+// hostile archive can't fill the disk
+const MAX_SOURCE_BYTES: u64 = 100 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    Directory,
+    Zip,
+    TarGz,
+    Tar,
+}
+
+pub fn source_kind(path: &Path) -> Result<SourceKind, String> {
+    if path.is_dir() {
+        return Ok(SourceKind::Directory);
+    }
+
+    let name = path.file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if name.ends_with(".zip") || name.ends_with(".skill") {
+        Ok(SourceKind::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Ok(SourceKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Ok(SourceKind::Tar)
+    } else {
+        Err(format!("Unsupported skill source: {}", path.display()))
+    }
+}
+
+// Archive tools add metadata entries that are never part of a skill
+fn is_ignored(path: &Path, include_hidden: bool) -> bool {
+    path.components().any(|c| {
+        let part = c.as_os_str().to_string_lossy();
+        hashing::is_junk_name(&part) || (part.starts_with('.') && !include_hidden)
+    })
+}
+
+struct SizeBudget(u64);
+
+impl SizeBudget {
+    fn take(&mut self, bytes: u64) -> Result<(), String> {
+        self.0 = self.0.checked_sub(bytes)
+            .ok_or_else(|| format!("Skill source is larger than {} MB", MAX_SOURCE_BYTES / 1024 / 1024))?;
+        Ok(())
+    }
+}
+
+fn read_directory(dir: &Path, include_hidden: bool, budget: &mut SizeBudget) -> Result<Vec<SkillFile>, String> {
+    let relatives = if include_hidden { hashing::list_files_with_hidden(dir)? } else { hashing::list_files(dir)? };
+    let mut files = Vec::new();
+    for relative in relatives {
+        let path = dir.join(&relative);
+        let contents = fs::read(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        budget.take(contents.len() as u64)?;
+        let mode = fs::metadata(&path).ok().and_then(|m| installer::file_mode(&m));
+        files.push(SkillFile { path: PathBuf::from(relative), contents, mode });
+    }
+    Ok(files)
+}
+
+fn read_limited(reader: impl Read, size_hint: u64, budget: &mut SizeBudget) -> Result<Vec<u8>, String> {
+    budget.take(size_hint)?;
+    let mut contents = Vec::new();
+    reader.take(MAX_SOURCE_BYTES + 1)
+        .read_to_end(&mut contents)
+        .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+    if contents.len() as u64 > size_hint {
+        budget.take(contents.len() as u64 - size_hint)?;
+    }
+    Ok(contents)
+}
+
+fn read_zip(path: &Path, include_hidden: bool, budget: &mut SizeBudget) -> Result<Vec<SkillFile>, String> {
+    let file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read zip archive: {}", e))?;
+
+    let mut files = Vec::new();
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)
+            .map_err(|e| format!("Failed to read zip archive: {}", e))?;
+        if !entry.is_file() {
+            continue;
+        }
+
+        let name = entry.name().to_string();
+        let relative = installer::safe_relative_path(&name)
+            .ok_or_else(|| format!("Refusing to extract {} outside the skill directory", name))?;
+        if is_ignored(&relative, include_hidden) {
+            continue;
+        }
+
+        let size = entry.size();
+        let mode = entry.unix_mode().map(|m| m & 0o777);
+        let contents = read_limited(entry, size, budget)?;
+        files.push(SkillFile { path: relative, contents, mode });
+    }
+    Ok(files)
+}
+
+fn read_tar(reader: impl Read, include_hidden: bool, budget: &mut SizeBudget) -> Result<Vec<SkillFile>, String> {
+    let mut archive = tar::Archive::new(reader);
+    let entries = archive.entries()
+        .map_err(|e| format!("Failed to read tar archive: {}", e))?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read tar archive: {}", e))?;
+        // Links, devices and directories are skipped; only regular file contents are kept
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let name = entry.path()
+            .map_err(|e| format!("Failed to read tar archive: {}", e))?
+            .to_string_lossy()
+            .to_string();
+        let relative = installer::safe_relative_path(&name)
+            .ok_or_else(|| format!("Refusing to extract {} outside the skill directory", name))?;
+        if is_ignored(&relative, include_hidden) {
+            continue;
+        }
+
+        let size = entry.size();
+        let mode = entry.header().mode().ok().map(|m| m & 0o777);
+        let contents = read_limited(entry, size, budget)?;
+        files.push(SkillFile { path: relative, contents, mode });
+    }
+    Ok(files)
+}
+
+pub fn read_source(path: &Path) -> Result<Vec<SkillFile>, String> {
+    read_source_with(path, false)
+}
+
+// Same as read_source, but keeps dotfiles (plugins need .claude-plugin/ and .mcp.json)
+pub fn read_source_with(path: &Path, include_hidden: bool) -> Result<Vec<SkillFile>, String> {
+    let mut budget = SizeBudget(MAX_SOURCE_BYTES);
+
+    match source_kind(path)? {
+        SourceKind::Directory => read_directory(path, include_hidden, &mut budget),
+        SourceKind::Zip => read_zip(path, include_hidden, &mut budget),
+        SourceKind::TarGz => {
+            let file = fs::File::open(path)
+                .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            read_tar(flate2::read::GzDecoder::new(file), include_hidden, &mut budget)
+        }
+        SourceKind::Tar => {
+            let file = fs::File::open(path)
+                .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            read_tar(file, include_hidden, &mut budget)
+        }
+    }
+}
+
+// Picks the shallowest SKILL.md (archives often wrap the skill in one or more folders)
+// and re-roots the files beneath it. Returns the skill's folder name, if it had one.
+pub fn locate_skill(files: Vec<SkillFile>) -> Result<(Option<String>, Vec<SkillFile>), String> {
+    let mut candidates: Vec<&Path> = files.iter()
+        .map(|f| f.path.as_path())
+        .filter(|p| p.file_name().map(|n| n == SKILL_FILE).unwrap_or(false))
+        .collect();
+    candidates.sort_by_key(|p| p.components().count());
+
+    let shallowest = candidates.first()
+        .ok_or_else(|| format!("No {} found", SKILL_FILE))?;
+    let depth = shallowest.components().count();
+    let same_depth = candidates.iter().filter(|p| p.components().count() == depth).count();
+    if same_depth > 1 {
+        return Err(format!(
+            "Found {} skills at the same level; install them one at a time",
+            same_depth
+        ));
+    }
+
+    let root = shallowest.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+    let folder_name = root.file_name().map(|n| n.to_string_lossy().to_string());
+
+    let files = files.into_iter()
+        .filter_map(|f| {
+            let relative = f.path.strip_prefix(&root).ok()?.to_path_buf();
+            Some(SkillFile { path: relative, contents: f.contents, mode: f.mode })
+        })
+        .collect();
+
+    Ok((folder_name, files))
+}
+
+// Explicit name, then the frontmatter name, then the folder (or archive) name
+fn choose_name(
+    requested: Option<String>,
+    parsed: &skill_md::ParsedSkill,
+    folder_name: Option<String>,
+    source: &Path,
+) -> Result<String, String> {
+    let frontmatter_name = parsed.frontmatter.name.clone()
+        .filter(|n| skill_md::is_valid_skill_name(n));
+    let source_name = source.file_name().map(|n| {
+        let n = n.to_string_lossy().to_string();
+        [".tar.gz", ".tgz", ".tar", ".zip", ".skill"]
+            .iter()
+            .find_map(|ext| n.strip_suffix(ext).map(|s| s.to_string()))
+            .unwrap_or(n)
+    });
+
+    let name = requested.filter(|n| !n.is_empty())
+        .or(frontmatter_name)
+        .or(folder_name)
+        .or(source_name)
+        .ok_or_else(|| "Could not determine a name for the skill".to_string())?;
+    clients::validate_skill_name(&name)?;
+    Ok(name)
+}
+
+pub fn load_skill_source(path: &Path, name: Option<String>) -> Result<(String, Vec<SkillFile>), String> {
+    // Picking the SKILL.md itself means "this folder"
+    let path = match path.parent() {
+        Some(parent) if path.file_name().map(|n| n == SKILL_FILE).unwrap_or(false) => parent,
+        _ => path,
+    };
+
+    let files = read_source(path)?;
+    let (folder_name, files) = locate_skill(files)?;
+    let parsed = installer::validate_skill_files(&files)?;
+    let name = choose_name(name, &parsed, folder_name, path)?;
+    Ok((name, files))
+}
+
+#[tauri::command]
+pub async fn install_skill_from_path(
+    app: AppHandle,
+    path: String,
+    name: Option<String>,
+    client: Client,
+    skill_type: SkillType,
+    project_path: Option<String>,
+    mode: Option<InstallMode>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let source = clients::expand_home(&path)?;
+        let (name, files) = load_skill_source(&source, name)?;
+        let targets = [InstallTarget { client, skill_type }];
+        let lock_source = LockSource::Path { path: clients::path_to_string(&source)? };
+
+        let results = installer::install_prepared(
+            &app,
+            &files,
+            &name,
+            &targets,
+            project_path.as_deref(),
+            mode.unwrap_or_default(),
+            Some(&lock_source),
+        )?;
+        installer::single_result(results)
+    })
+    .await
+    .map_err(|e| format!("Install task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, u32)]) {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, mode) in entries {
+            let options = zip::write::SimpleFileOptions::default().unix_permissions(*mode);
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(b"contents").unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn write_tar(path: &Path, entries: &[(&str, u32)]) {
+        let mut builder = tar::Builder::new(fs::File::create(path).unwrap());
+        for (name, mode) in entries {
+            let mut header = tar::Header::new_gnu();
+            // Written into the raw name field so unsafe names reach the reader as-is
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(8);
+            header.set_mode(*mode);
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_cksum();
+            builder.append(&header, &b"contents"[..]).unwrap();
+        }
+        builder.finish().unwrap();
+    }
+
+    fn file(path: &str) -> SkillFile {
+        SkillFile { path: PathBuf::from(path), contents: Vec::new(), mode: None }
+    }
+
+    fn modes(files: &[SkillFile]) -> Vec<(String, Option<u32>)> {
+        files.iter().map(|f| (f.path.to_string_lossy().replace('\\', "/"), f.mode)).collect()
+    }
+
+    #[test]
+    fn archives_keep_file_modes() {
+        let dir = temp_dir("archive-modes");
+        let entries = [("SKILL.md", 0o644), ("scripts/run.sh", 0o755)];
+        write_zip(&dir.join("skill.zip"), &entries);
+        write_tar(&dir.join("skill.tar"), &entries);
+
+        let expected = vec![
+            ("SKILL.md".to_string(), Some(0o644)),
+            ("scripts/run.sh".to_string(), Some(0o755)),
+        ];
+        let zip = read_source(&dir.join("skill.zip"));
+        let tar = read_source(&dir.join("skill.tar"));
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(modes(&zip.unwrap()), expected);
+        assert_eq!(modes(&tar.unwrap()), expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn directories_keep_file_modes_through_an_install() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("directory-modes");
+        let source = dir.join("source");
+        fs::create_dir_all(source.join("scripts")).unwrap();
+        fs::write(source.join(SKILL_FILE), "skill").unwrap();
+        fs::write(source.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        fs::set_permissions(source.join("scripts/run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+
+        let files = read_source(&source).unwrap();
+        installer::write_skill_files(&dir.join("target"), &files).unwrap();
+        let mode = fs::metadata(dir.join("target/scripts/run.sh")).unwrap().permissions().mode() & 0o777;
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(mode, 0o755);
+    }
+
+    #[test]
+    fn archives_refuse_paths_outside_the_skill() {
+        let dir = temp_dir("archive-traversal");
+        for (index, name) in ["../evil.sh", "skill/../../evil.sh", "/etc/evil", "C:\\evil.sh", "..\\evil.sh"].iter().enumerate() {
+            let zip = dir.join(format!("{}.zip", index));
+            write_zip(&zip, &[("SKILL.md", 0o644), (name, 0o644)]);
+            let tar = dir.join(format!("{}.tar", index));
+            write_tar(&tar, &[("SKILL.md", 0o644), (name, 0o644)]);
+
+            assert!(read_source(&zip).is_err(), "zip accepted {}", name);
+            assert!(read_source(&tar).is_err(), "tar accepted {}", name);
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn locate_skill_picks_the_shallowest_skill() {
+        let files = vec![
+            file("wrapper/pdf/SKILL.md"),
+            file("wrapper/pdf/scripts/run.sh"),
+            file("wrapper/pdf/examples/demo/SKILL.md"),
+            file("README.md"),
+        ];
+        let (folder, files) = locate_skill(files).unwrap();
+
+        assert_eq!(folder.as_deref(), Some("pdf"));
+        let paths: Vec<String> = modes(&files).into_iter().map(|(p, _)| p).collect();
+        assert_eq!(paths, ["SKILL.md", "scripts/run.sh", "examples/demo/SKILL.md"]);
+    }
+
+    #[test]
+    fn locate_skill_keeps_root_skills_whole() {
+        let (folder, files) = locate_skill(vec![file("SKILL.md"), file("nested/SKILL.md")]).unwrap();
+        assert_eq!(folder, None);
+        assert_eq!(files.len(), 2);
+    }
+
+    #[test]
+    fn locate_skill_refuses_ambiguous_layouts() {
+        assert!(locate_skill(vec![file("a/SKILL.md"), file("b/SKILL.md")]).is_err());
+        assert!(locate_skill(vec![file("README.md")]).is_err());
+    }
+}
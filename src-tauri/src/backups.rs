@@ -0,0 +1,481 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::clients::{self, ALL_CLIENTS};
+use crate::hashing;
+use crate::installed;
+use crate::installer::{self, SkillFile};
+use crate::remote::SKILL_FILE;
+
+// Each backup is <app data>/backups/<id>/ holding backup.json and a files/ copy of the skill
+const BACKUPS_DIR: &str = "backups";
+const BACKUP_META_FILE: &str = "backup.json";
+const BACKUP_FILES_DIR: &str = "files";
+const SETTINGS_STORE: &str = "skill-backups.json";
+const SETTINGS_KEY: &str = "settings";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackupReason {
+    Install,
+    Uninstall,
+    LockSync,
+    Restore,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSettings {
+    // Backups kept per skill path, newest first
+    pub max_per_skill: usize,
+    // Older backups are pruned, except the newest one of each skill. None keeps them forever.
+    pub max_age_days: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub id: String,
+    pub name: String,
+    // Skill directory the backup was taken from
+    pub path: String,
+    pub reason: BackupReason,
+    pub created_at: String,
+    // Milliseconds since the Unix epoch, used for ordering and retention
+    pub timestamp: u64,
+    pub hash: String,
+    pub size: u64,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        BackupSettings {
+            max_per_skill: 10,
+            max_age_days: Some(30),
+        }
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+pub fn load_settings(app: &AppHandle) -> Result<BackupSettings, String> {
+    let store = app.store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open backup settings: {}", e))?;
+
+    match store.get(SETTINGS_KEY) {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse backup settings: {}", e)),
+        None => Ok(BackupSettings::default()),
+    }
+}
+
+fn save_settings(app: &AppHandle, settings: &BackupSettings) -> Result<(), String> {
+    let store = app.store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open backup settings: {}", e))?;
+    let value = serde_json::to_value(settings)
+        .map_err(|e| format!("Failed to serialize backup settings: {}", e))?;
+
+    store.set(SETTINGS_KEY, value);
+    store.save()
+        .map_err(|e| format!("Failed to save backup settings: {}", e))
+}
+
+pub struct BackupArea {
+    root: PathBuf,
+    settings: BackupSettings,
+}
+
+impl BackupArea {
+    pub fn open(app: &AppHandle) -> Result<Self, String> {
+        let root = app.path()
+            .app_data_dir()
+            .map(|dir| dir.join(BACKUPS_DIR))
+            .map_err(|e| format!("Could not find app data directory: {}", e))?;
+        Ok(BackupArea { root, settings: load_settings(app)? })
+    }
+
+    fn read_info(&self, id: &str) -> Option<BackupInfo> {
+        let bytes = fs::read(self.root.join(id).join(BACKUP_META_FILE)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    // Newest first
+    pub fn list(&self) -> Vec<BackupInfo> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+
+        let mut backups: Vec<BackupInfo> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let id = entry.file_name().to_string_lossy().to_string();
+                if id.starts_with('.') {
+                    return None;
+                }
+                self.read_info(&id)
+            })
+            .collect();
+
+        backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| b.id.cmp(&a.id)));
+        backups
+    }
+
+    fn backup_dir(&self, id: &str) -> Result<PathBuf, String> {
+        clients::validate_skill_name(id)?;
+        let dir = self.root.join(id);
+        if !dir.join(BACKUP_META_FILE).is_file() {
+            return Err(format!("Backup {} not found", id));
+        }
+        Ok(dir)
+    }
+
+    fn unique_id(&self, timestamp: u64, name: &str) -> String {
+        let base = format!("{}-{}", timestamp, name);
+        let mut id = base.clone();
+        let mut counter = 1;
+        while fs::symlink_metadata(self.root.join(&id)).is_ok() {
+            counter += 1;
+            id = format!("{}-{}", base, counter);
+        }
+        id
+    }
+
+    // Copies the skill at `skill_dir` into the backup area before it gets replaced or removed.
+    // Links and missing directories have nothing of their own to lose, so they are skipped.
+    pub fn snapshot(&self, skill_dir: &Path, reason: BackupReason) -> Result<Option<BackupInfo>, String> {
+        match fs::symlink_metadata(skill_dir) {
+            Ok(metadata) if metadata.is_dir() => {}
+            _ => return Ok(None),
+        }
+        if !skill_dir.join(SKILL_FILE).is_file() {
+            return Ok(None);
+        }
+
+        let files = read_files(skill_dir).map_err(|e| format!("Failed to back up: {}", e))?;
+
+        let name = skill_dir.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "skill".to_string());
+        let timestamp = now_millis();
+        let info = BackupInfo {
+            id: self.unique_id(timestamp, &name),
+            name,
+            path: clients::path_to_string(skill_dir)?,
+            reason,
+            created_at: installed::timestamp_now(),
+            timestamp,
+            hash: hashing::dir_hash(skill_dir)?,
+            size: files.iter().map(|f| f.contents.len() as u64).sum(),
+        };
+
+        // Written to a hidden folder first so a half-written backup never shows up in the list
+        let target = self.root.join(&info.id);
+        let partial = installer::sibling_temp_path(&target, "partial");
+        let written = installer::write_skill_files(&partial.join(BACKUP_FILES_DIR), &files)
+            .and_then(|_| {
+                let meta = serde_json::to_vec_pretty(&info)
+                    .map_err(|e| format!("Failed to serialize backup: {}", e))?;
+                fs::write(partial.join(BACKUP_META_FILE), meta)
+                    .map_err(|e| format!("Failed to write backup: {}", e))
+            })
+            .and_then(|_| fs::rename(&partial, &target).map_err(|e| format!("Failed to write backup: {}", e)));
+        if let Err(e) = written {
+            let _ = installer::remove_path(&partial);
+            return Err(e);
+        }
+
+        self.prune();
+        Ok(Some(info))
+    }
+
+    // Applies the retention settings; returns the ids that were deleted
+    pub fn prune(&self) -> Vec<String> {
+        let max_age_ms = self.settings.max_age_days.map(|days| days.saturating_mul(24 * 60 * 60 * 1000));
+        let now = now_millis();
+        let mut seen: Vec<(String, usize)> = Vec::new();
+        let mut removed = Vec::new();
+
+        for backup in self.list() {
+            let index = match seen.iter().position(|(path, _)| path == &backup.path) {
+                Some(position) => {
+                    seen[position].1 += 1;
+                    seen[position].1
+                }
+                None => {
+                    seen.push((backup.path.clone(), 0));
+                    0
+                }
+            };
+
+            let too_many = index >= self.settings.max_per_skill.max(1);
+            let too_old = index > 0 && max_age_ms.map(|max| now.saturating_sub(backup.timestamp) > max).unwrap_or(false);
+            if (too_many || too_old) && installer::remove_path(&self.root.join(&backup.id)).is_ok() {
+                removed.push(backup.id);
+            }
+        }
+
+        removed
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let dir = self.backup_dir(id)?;
+        installer::remove_path(&dir)
+    }
+
+    // Puts a backup back in place, backing up whatever is there now first
+    pub fn restore(&self, id: &str, target: Option<&Path>) -> Result<PathBuf, String> {
+        let dir = self.backup_dir(id)?;
+        let info = self.read_info(id)
+            .ok_or_else(|| format!("Backup {} is damaged", id))?;
+        let target = target.map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from(&info.path));
+
+        let files = read_files(&dir.join(BACKUP_FILES_DIR))
+            .map_err(|e| format!("Failed to read backup: {}", e))?;
+        installer::validate_skill_files(&files)?;
+        check_restore_target(&info, &target)?;
+
+        self.snapshot(&target, BackupReason::Restore)?;
+        installer::install_files(&target, &files)?;
+        Ok(target)
+    }
+}
+
+// Everything in a skill folder, dotfiles and modes included: restoring replaces the whole
+// folder, so a file left out of the backup would be lost
+fn read_files(dir: &Path) -> Result<Vec<SkillFile>, String> {
+    let mut files = Vec::new();
+    for relative in hashing::list_files_with_hidden(dir)? {
+        let path = dir.join(&relative);
+        let contents = fs::read(&path)
+            .map_err(|e| format!("{}: {}", relative, e))?;
+        let mode = fs::metadata(&path).ok().and_then(|m| installer::file_mode(&m));
+        files.push(SkillFile { path: PathBuf::from(relative), contents, mode });
+    }
+    Ok(files)
+}
+
+// Restoring replaces the target wholesale, so it has to be the folder the backup came from or a
+// skill folder directly inside a client's skills directory, and must not hold anything but a skill
+fn check_restore_target(info: &BackupInfo, target: &Path) -> Result<(), String> {
+    if target != Path::new(&info.path) {
+        let in_skill_root = target.parent()
+            .map(|parent| ALL_CLIENTS.iter().any(|client| {
+                parent.ends_with(client.personal_skill_dir())
+                    || client.project_skill_dir().map(|dir| parent.ends_with(dir)).unwrap_or(false)
+            }))
+            .unwrap_or(false);
+        let name = target.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if !in_skill_root || clients::validate_skill_name(name).is_err() {
+            return Err(format!("{} is not a skill folder inside a skills directory", target.display()));
+        }
+    }
+
+    let occupied = match fs::symlink_metadata(target) {
+        Ok(metadata) if metadata.is_dir() => fs::read_dir(target)
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(true),
+        Ok(metadata) => !metadata.file_type().is_symlink(),
+        Err(_) => false,
+    };
+    if occupied && !target.join(SKILL_FILE).is_file() {
+        return Err(format!("{} is not a skill; refusing to replace it", target.display()));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_skill_backups(app: AppHandle, path: Option<String>) -> Result<Vec<BackupInfo>, String> {
+    let area = BackupArea::open(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = path.map(|p| clients::expand_home(&p)).transpose()?;
+        Ok(area.list()
+            .into_iter()
+            .filter(|b| path.as_ref().map(|p| Path::new(&b.path) == p).unwrap_or(true))
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("Listing backups failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn restore_skill_backup(app: AppHandle, id: String, path: Option<String>) -> Result<String, String> {
+    let area = BackupArea::open(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let target = path.map(|p| clients::expand_home(&p)).transpose()?;
+        let restored = area.restore(&id, target.as_deref())?;
+        clients::path_to_string(&restored)
+    })
+    .await
+    .map_err(|e| format!("Restore failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn delete_skill_backup(app: AppHandle, id: String) -> Result<(), String> {
+    let area = BackupArea::open(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || area.delete(&id))
+        .await
+        .map_err(|e| format!("Deleting backup failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn get_backup_settings(app: AppHandle) -> Result<BackupSettings, String> {
+    load_settings(&app)
+}
+
+// Saves new retention settings and prunes right away; returns how many backups were removed
+#[tauri::command]
+pub async fn set_backup_settings(app: AppHandle, settings: BackupSettings) -> Result<usize, String> {
+    save_settings(&app, &settings)?;
+    let area = BackupArea::open(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || Ok(area.prune().len()))
+        .await
+        .map_err(|e| format!("Pruning backups failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn area(root: &Path, settings: BackupSettings) -> BackupArea {
+        BackupArea { root: root.join(BACKUPS_DIR), settings }
+    }
+
+    fn write_skill(dir: &Path, body: &str) {
+        let file = |path: &str, contents: &str| SkillFile { path: PathBuf::from(path), contents: contents.into(), mode: None };
+        installer::install_files(dir, &[
+            file(SKILL_FILE, &format!("---\nname: pdf\ndescription: {}\n---\n", body)),
+            file(".env.example", "TOKEN="),
+            file("scripts/run.sh", "#!/bin/sh\n"),
+        ]).unwrap();
+    }
+
+    // A backup as snapshot leaves it, with a chosen timestamp
+    fn fake_backup(area: &BackupArea, id: &str, path: &str, timestamp: u64) {
+        let info = BackupInfo {
+            id: id.to_string(),
+            name: "pdf".to_string(),
+            path: path.to_string(),
+            reason: BackupReason::Install,
+            created_at: String::new(),
+            timestamp,
+            hash: String::new(),
+            size: 0,
+        };
+        let dir = area.root.join(id);
+        fs::create_dir_all(dir.join(BACKUP_FILES_DIR)).unwrap();
+        fs::write(dir.join(BACKUP_META_FILE), serde_json::to_vec(&info).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn snapshot_and_restore_round_trip_every_file() {
+        let root = temp_dir("backup-round-trip");
+        let area = area(&root, BackupSettings::default());
+        let skill = root.join(".claude/skills/pdf");
+        write_skill(&skill, "original");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(skill.join("scripts/run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let info = area.snapshot(&skill, BackupReason::Install).unwrap().unwrap();
+        let original = hashing::list_files_with_hidden(&skill).unwrap();
+        write_skill(&skill, "edited");
+        fs::remove_file(skill.join(".env.example")).unwrap();
+
+        let restored = area.restore(&info.id, None).unwrap();
+        let files = hashing::list_files_with_hidden(&restored).unwrap();
+        let skill_md = fs::read_to_string(restored.join(SKILL_FILE)).unwrap();
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            fs::metadata(restored.join("scripts/run.sh")).unwrap().permissions().mode() & 0o777
+        };
+        let backups = area.list();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(files, original);
+        assert!(files.contains(&".env.example".to_string()));
+        assert!(skill_md.contains("original"));
+        #[cfg(unix)]
+        assert_eq!(mode, 0o755);
+        // The edited copy was backed up before being replaced
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].reason, BackupReason::Restore);
+    }
+
+    #[test]
+    fn snapshot_skips_folders_that_are_not_skills() {
+        let root = temp_dir("backup-not-a-skill");
+        let area = area(&root, BackupSettings::default());
+        fs::create_dir_all(root.join("empty")).unwrap();
+
+        let empty = area.snapshot(&root.join("empty"), BackupReason::Install).unwrap();
+        let missing = area.snapshot(&root.join("missing"), BackupReason::Install).unwrap();
+        let _ = fs::remove_dir_all(&root);
+        assert!(empty.is_none());
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn restore_refuses_targets_outside_skill_directories() {
+        let root = temp_dir("backup-restore-target");
+        let area = area(&root, BackupSettings::default());
+        let skill = root.join(".claude/skills/pdf");
+        write_skill(&skill, "original");
+        let info = area.snapshot(&skill, BackupReason::Install).unwrap().unwrap();
+        fs::create_dir_all(root.join("documents")).unwrap();
+        fs::write(root.join("documents/notes.txt"), "keep me").unwrap();
+        fs::create_dir_all(root.join(".claude/skills/notes")).unwrap();
+        fs::write(root.join(".claude/skills/notes/notes.txt"), "keep me").unwrap();
+
+        let outside = area.restore(&info.id, Some(&root.join("documents")));
+        let not_a_skill = area.restore(&info.id, Some(&root.join(".claude/skills/notes")));
+        let elsewhere = area.restore(&info.id, Some(&root.join(".cursor/skills/pdf")));
+        let _ = fs::remove_dir_all(&root);
+        assert!(outside.is_err());
+        assert!(not_a_skill.is_err());
+        assert!(elsewhere.is_ok());
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_backup_of_each_skill() {
+        let root = temp_dir("backup-prune");
+        let day = 24 * 60 * 60 * 1000;
+        let now = now_millis();
+        let area = area(&root, BackupSettings { max_per_skill: 2, max_age_days: Some(7) });
+        fake_backup(&area, "a-new", "/skills/a", now);
+        fake_backup(&area, "a-old", "/skills/a", now - 10 * day);
+        fake_backup(&area, "b-new", "/skills/b", now - 1);
+        fake_backup(&area, "b-mid", "/skills/b", now - 2);
+        fake_backup(&area, "b-extra", "/skills/b", now - 3);
+        fake_backup(&area, "c-ancient", "/skills/c", now - 100 * day);
+
+        let mut removed = area.prune();
+        removed.sort();
+        let kept: Vec<String> = area.list().into_iter().map(|b| b.id).collect();
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(removed, ["a-old", "b-extra"]);
+        assert_eq!(kept, ["a-new", "b-new", "b-mid", "c-ancient"]);
+    }
+}
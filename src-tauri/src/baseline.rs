@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::clients;
+use crate::hashing;
+use crate::installed;
+use crate::installer::{ClientInstallResult, InstallStatus};
+
+// File hashes of each skill directory as Skiller last wrote it, keyed by path. Comparing
+// against them tells local edits apart from upstream changes.
+const BASELINE_STORE: &str = "skill-baselines.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Baseline {
+    pub recorded_at: String,
+    pub files: BTreeMap<String, String>,
+}
+
+pub fn load_baseline(app: &AppHandle, path: &Path) -> Result<Option<Baseline>, String> {
+    let key = clients::path_to_string(path)?;
+    let store = app.store(BASELINE_STORE)
+        .map_err(|e| format!("Failed to open baseline store: {}", e))?;
+
+    Ok(store.get(&key).and_then(|value| serde_json::from_value(value).ok()))
+}
+
+pub fn record_baselines(app: &AppHandle, results: &[ClientInstallResult]) -> Result<(), String> {
+    let store = app.store(BASELINE_STORE)
+        .map_err(|e| format!("Failed to open baseline store: {}", e))?;
+
+    for result in results.iter().filter(|r| r.status != InstallStatus::Failed) {
+        let Some(path) = &result.path else { continue };
+        let Ok(files) = hashing::file_hashes(Path::new(path)) else { continue };
+        let baseline = Baseline {
+            recorded_at: installed::timestamp_now(),
+            files,
+        };
+        let value = serde_json::to_value(&baseline)
+            .map_err(|e| format!("Failed to serialize baseline: {}", e))?;
+        store.set(path.clone(), value);
+    }
+
+    store.save()
+        .map_err(|e| format!("Failed to save baseline store: {}", e))
+}
+
+pub fn forget_baseline(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let key = clients::path_to_string(path)?;
+    let store = app.store(BASELINE_STORE)
+        .map_err(|e| format!("Failed to open baseline store: {}", e))?;
+
+    if store.delete(&key) {
+        store.save()
+            .map_err(|e| format!("Failed to save baseline store: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::archive;
+use crate::clients::{self, Client, SkillType};
+use crate::commands;
+use crate::hashing;
+use crate::installed::{self, InstalledItem};
+use crate::installer::{self, ClientInstallResult, InstallMode, InstallStatus, InstallTarget, SkillFile};
+use crate::inventory::{self, InventoryEntry, SkillStatus};
+
+const MANIFEST_FILE: &str = "skiller-bundle.json";
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    pub version: u32,
+    pub created_at: String,
+    pub skills: Vec<BundleSkill>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSkill {
+    pub name: String,
+    // Folder inside the archive holding this skill's files
+    pub dir: String,
+    pub clients: Vec<Client>,
+    pub skill_type: SkillType,
+    // Registry id from the installed store, when the skill was installed through Skiller
+    pub source: Option<String>,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleImportResult {
+    pub name: String,
+    pub results: Vec<ClientInstallResult>,
+    pub error: Option<String>,
+}
+
+fn find_entry<'a>(entries: &'a [InventoryEntry], path: &str) -> Result<&'a InventoryEntry, String> {
+    let wanted = clients::expand_home(path)?;
+    entries.iter()
+        .find(|e| e.status != SkillStatus::MissingOnDisk && Path::new(&e.path) == wanted)
+        .ok_or_else(|| format!("{} is not an installed skill", path))
+}
+
+fn write_bundle(target: &Path, manifest: &BundleManifest, skills: &[(String, Vec<SkillFile>)]) -> Result<(), String> {
+    let file = fs::File::create(target)
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let manifest_json = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize bundle manifest: {}", e))?;
+    zip.start_file(MANIFEST_FILE, options)
+        .and_then(|_| zip.write_all(&manifest_json).map_err(Into::into))
+        .map_err(|e| format!("Failed to write bundle: {}", e))?;
+
+    for (dir, files) in skills {
+        for file in files {
+            let name = format!("{}/{}", dir, file.path.to_string_lossy().replace('\\', "/"));
+            let options = match file.mode {
+                Some(mode) => options.unix_permissions(mode),
+                None => options,
+            };
+            zip.start_file(name, options)
+                .and_then(|_| zip.write_all(&file.contents).map_err(Into::into))
+                .map_err(|e| format!("Failed to write bundle: {}", e))?;
+        }
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to write bundle: {}", e))?;
+    Ok(())
+}
+
+pub fn export_bundle(
+    entries: &[InventoryEntry],
+    paths: &[String],
+    target_dir: &Path,
+) -> Result<PathBuf, String> {
+    if paths.is_empty() {
+        return Err("No skills selected".to_string());
+    }
+
+    let mut manifest = BundleManifest {
+        version: BUNDLE_VERSION,
+        created_at: installed::timestamp_now(),
+        skills: Vec::new(),
+    };
+    let mut contents = Vec::new();
+
+    for (index, path) in paths.iter().enumerate() {
+        let entry = find_entry(entries, path)?;
+        let skill_dir = Path::new(&entry.path);
+        let dir = format!("skills/{}-{}", index + 1, entry.name);
+
+        let mut files = Vec::new();
+        for relative in hashing::list_files(skill_dir)? {
+            let path = skill_dir.join(&relative);
+            let bytes = fs::read(&path)
+                .map_err(|e| format!("Failed to read {}: {}", relative, e))?;
+            let mode = fs::metadata(&path).ok().and_then(|m| installer::file_mode(&m));
+            files.push(SkillFile { path: PathBuf::from(relative), contents: bytes, mode });
+        }
+
+        manifest.skills.push(BundleSkill {
+            name: entry.name.clone(),
+            dir: dir.clone(),
+            clients: entry.clients.clone(),
+            skill_type: entry.skill_type,
+            source: entry.installed_id.clone(),
+            hash: hashing::dir_hash(skill_dir)?,
+        });
+        contents.push((dir, files));
+    }
+
+    fs::create_dir_all(target_dir)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let target = reserve_target(target_dir, &manifest.created_at)?;
+
+    let partial = installer::sibling_temp_path(&target, "partial");
+    if let Err(e) = write_bundle(&partial, &manifest, &contents) {
+        let _ = fs::remove_file(&partial);
+        let _ = fs::remove_file(&target);
+        return Err(e);
+    }
+    fs::rename(&partial, &target)
+        .map_err(|e| {
+            let _ = fs::remove_file(&target);
+            format!("Failed to write bundle: {}", e)
+        })?;
+
+    Ok(target)
+}
+
+// Claims a file name no other export is using by creating it empty; exports in the same
+// second get a numbered suffix instead of overwriting each other
+fn reserve_target(target_dir: &Path, created_at: &str) -> Result<PathBuf, String> {
+    // "2024-05-01T12:30:00.000Z" -> "2024-05-01T12-30-00"
+    let stamp = created_at.get(..19).unwrap_or(created_at).replace(':', "-");
+    for attempt in 1..1000 {
+        let name = if attempt == 1 {
+            format!("skiller-bundle-{}.zip", stamp)
+        } else {
+            format!("skiller-bundle-{}-{}.zip", stamp, attempt)
+        };
+        let target = target_dir.join(name);
+        match fs::OpenOptions::new().write(true).create_new(true).open(&target) {
+            Ok(_) => return Ok(target),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create file: {}", e)),
+        }
+    }
+    Err("Failed to find a free bundle file name".to_string())
+}
+
+pub fn read_bundle(path: &Path) -> Result<(BundleManifest, Vec<SkillFile>), String> {
+    let files = archive::read_source(path)?;
+    let manifest_file = files.iter()
+        .find(|f| f.path == Path::new(MANIFEST_FILE))
+        .ok_or_else(|| format!("{} is not a Skiller bundle", path.display()))?;
+    let manifest: BundleManifest = serde_json::from_slice(&manifest_file.contents)
+        .map_err(|e| format!("Invalid bundle manifest: {}", e))?;
+
+    if manifest.version > BUNDLE_VERSION {
+        return Err(format!("Bundle version {} is newer than this version of Skiller supports", manifest.version));
+    }
+    Ok((manifest, files))
+}
+
+fn import_skill(
+    app: &AppHandle,
+    skill: &BundleSkill,
+    files: &[SkillFile],
+    project_path: Option<&str>,
+    mode: InstallMode,
+) -> Result<Vec<ClientInstallResult>, String> {
+    clients::validate_skill_name(&skill.name)?;
+    let prefix = Path::new(&skill.dir);
+    let skill_files: Vec<SkillFile> = files.iter()
+        .filter_map(|f| {
+            let relative = f.path.strip_prefix(prefix).ok()?;
+            Some(SkillFile { path: relative.to_path_buf(), contents: f.contents.clone(), mode: f.mode })
+        })
+        .collect();
+    installer::validate_skill_files(&skill_files)?;
+
+    let hashes: BTreeMap<String, String> = skill_files.iter()
+        .map(|f| (f.path.to_string_lossy().replace('\\', "/"), hashing::sha256_hex(&f.contents)))
+        .collect();
+    if hashing::combined_hash(&hashes) != skill.hash {
+        return Err(format!("{} in the bundle doesn't match its recorded hash; the bundle is damaged or was edited", skill.name));
+    }
+
+    let targets: Vec<InstallTarget> = skill.clients.iter()
+        .map(|&client| InstallTarget { client, skill_type: skill.skill_type })
+        .collect();
+    let mut results = installer::install_prepared(app, &skill_files, &skill.name, &targets, project_path, mode, None)?;
+
+    if let Some(source) = &skill.source {
+        let installed = results.iter_mut().find(|r| r.status != InstallStatus::Failed);
+        if let Some(result) = installed {
+            // The files are already written, so a failed record is a warning like the installer's own
+            let recorded = installed::add_installed(app, InstalledItem {
+                id: source.clone(),
+                kind: "skill".to_string(),
+                name: skill.name.clone(),
+                installed_at: installed::timestamp_now(),
+                client: Some(result.client),
+                skill_type: Some(skill.skill_type),
+                path: None,
+            });
+            if let Err(e) = recorded {
+                result.warnings.push(format!("Failed to record the installed source: {}", e));
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn export_skill_bundle(
+    app: AppHandle,
+    paths: Vec<String>,
+    project_paths: Option<Vec<String>>,
+    download_path: Option<String>,
+) -> Result<String, String> {
+    let project_paths = project_paths.unwrap_or_default();
+    let records = installed::load_installed(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let discovered = inventory::discover_skills(&project_paths);
+        let entries = inventory::reconcile(&discovered, &records, &project_paths)?;
+        let target_dir = commands::resolve_download_dir(download_path)?;
+        let target = export_bundle(&entries, &paths, &target_dir)?;
+        clients::path_to_string(&target)
+    })
+    .await
+    .map_err(|e| format!("Export failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn import_skill_bundle(
+    app: AppHandle,
+    path: String,
+    project_path: Option<String>,
+    mode: Option<InstallMode>,
+) -> Result<Vec<BundleImportResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (manifest, files) = read_bundle(&clients::expand_home(&path)?)?;
+        let mode = mode.unwrap_or_default();
+
+        Ok(manifest.skills.iter()
+            .map(|skill| match import_skill(&app, skill, &files, project_path.as_deref(), mode) {
+                Ok(results) => BundleImportResult { name: skill.name.clone(), results, error: None },
+                Err(e) => BundleImportResult { name: skill.name.clone(), results: Vec::new(), error: Some(e) },
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("Import failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve_target_numbers_clashes_and_accepts_short_timestamps() {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-bundle-target", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let first = reserve_target(&dir, "2024-05-01T12:30:00.000Z");
+        let second = reserve_target(&dir, "2024-05-01T12:30:00.500Z");
+        let short = reserve_target(&dir, "2024-05-01");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.unwrap(), dir.join("skiller-bundle-2024-05-01T12-30-00.zip"));
+        assert_eq!(second.unwrap(), dir.join("skiller-bundle-2024-05-01T12-30-00-2.zip"));
+        assert_eq!(short.unwrap(), dir.join("skiller-bundle-2024-05-01.zip"));
+    }
+}
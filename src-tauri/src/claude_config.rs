@@ -0,0 +1,342 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::clients;
+use crate::installer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileAction {
+    Created,
+    Modified,
+    Unchanged,
+}
+
+// A file Skiller wrote while changing Claude Code's setup, and which keys in it changed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchedFile {
+    pub path: String,
+    pub action: FileAction,
+    pub keys: Vec<String>,
+}
+
+// Tests that point CLAUDE_CONFIG_DIR at a temporary folder hold this, since the variable is
+// shared by every test thread
+#[cfg(test)]
+pub static CONFIG_DIR_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+// Claude Code honours CLAUDE_CONFIG_DIR in place of ~/.claude
+pub fn claude_dir() -> Result<PathBuf, String> {
+    match std::env::var("CLAUDE_CONFIG_DIR") {
+        Ok(dir) if !dir.is_empty() => clients::expand_home(&dir),
+        _ => Ok(clients::home_dir()?.join(".claude")),
+    }
+}
+
+pub fn plugins_dir() -> Result<PathBuf, String> {
+    Ok(claude_dir()?.join("plugins"))
+}
+
+pub fn user_settings_path() -> Result<PathBuf, String> {
+    Ok(claude_dir()?.join("settings.json"))
+}
+
+// ~/.claude.json holds user and per-project MCP servers; with CLAUDE_CONFIG_DIR it moves in there
+pub fn user_state_path() -> Result<PathBuf, String> {
+    match std::env::var("CLAUDE_CONFIG_DIR") {
+        Ok(dir) if !dir.is_empty() => Ok(clients::expand_home(&dir)?.join(".claude.json")),
+        _ => Ok(clients::home_dir()?.join(".claude.json")),
+    }
+}
+
+// None when the file doesn't exist; an empty file reads as an empty object
+pub fn read_json(path: &Path) -> Result<Option<Value>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    if content.trim().is_empty() {
+        return Ok(Some(Value::Object(Default::default())));
+    }
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+// The indentation used by an existing file, so rewriting it doesn't reformat everything
+fn detect_indent(content: &str) -> String {
+    content.lines()
+        .skip(1)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+        .to_string()
+}
+
+pub fn write_json(path: &Path, value: &Value) -> Result<FileAction, String> {
+    let existing = fs::read_to_string(path).ok();
+    let indent = existing.as_deref().map(detect_indent).unwrap_or_else(|| "  ".to_string());
+
+    let mut bytes = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut bytes, formatter);
+    value.serialize(&mut serializer)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+    let mut content = String::from_utf8(bytes)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+    if existing.as_deref().map(|c| c.ends_with('\n')).unwrap_or(true) {
+        content.push('\n');
+    }
+
+    if existing.as_deref() == Some(content.as_str()) {
+        return Ok(FileAction::Unchanged);
+    }
+
+    installer::ensure_parent_dir(path)?;
+    let partial = installer::sibling_temp_path(path, "partial");
+    fs::write(&partial, &content)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    fs::rename(&partial, path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    Ok(if existing.is_some() { FileAction::Modified } else { FileAction::Created })
+}
+
+// Reads the JSON object at `path` (or an empty one), lets `update` change it, and writes it
+// back only if something changed. Keys the update doesn't touch are kept as they were.
+pub fn update_json<F>(path: &Path, keys: Vec<String>, update: F) -> Result<TouchedFile, String>
+where
+    F: FnOnce(&mut serde_json::Map<String, Value>) -> Result<(), String>,
+{
+    update_json_with(path, keys, false, update)
+}
+
+// update_json, but with `dry_run` nothing is written and the result says what would happen
+pub fn update_json_with<F>(path: &Path, keys: Vec<String>, dry_run: bool, update: F) -> Result<TouchedFile, String>
+where
+    F: FnOnce(&mut serde_json::Map<String, Value>) -> Result<(), String>,
+{
+    let mut value = read_json(path)?.unwrap_or_else(|| Value::Object(Default::default()));
+    let object = value.as_object_mut()
+        .ok_or_else(|| format!("{} does not contain a JSON object", path.display()))?;
+
+    let before = object.clone();
+    update(object)?;
+    let action = if *object == before && path.exists() {
+        FileAction::Unchanged
+    } else if dry_run {
+        if path.exists() { FileAction::Modified } else { FileAction::Created }
+    } else {
+        write_json(path, &value)?
+    };
+
+    Ok(TouchedFile {
+        path: clients::path_to_string(path)?,
+        action,
+        keys: if action == FileAction::Unchanged { Vec::new() } else { keys },
+    })
+}
+
+// When the plugin already has a true/false entry and its key appears only once in the file,
+// the literal is swapped in the text itself so hand-written formatting survives untouched
+fn flip_in_place(path: &Path, id: &str, enabled: bool) -> Result<Option<FileAction>, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(None);
+    };
+    let current = serde_json::from_str::<Value>(&content).ok()
+        .and_then(|settings| settings.get("enabledPlugins")?.get(id)?.as_bool());
+    let Some(current) = current else {
+        return Ok(None);
+    };
+    if current == enabled {
+        return Ok(Some(FileAction::Unchanged));
+    }
+
+    let needle = serde_json::to_string(id)
+        .map_err(|e| format!("Failed to serialize {}: {}", id, e))?;
+    let mut matches = content.match_indices(&needle);
+    let (Some((start, _)), None) = (matches.next(), matches.next()) else {
+        return Ok(None);
+    };
+
+    let after_key = &content[start + needle.len()..];
+    let Some(after_colon) = after_key.trim_start().strip_prefix(':') else {
+        return Ok(None);
+    };
+    let value = after_colon.trim_start();
+    let old = if current { "true" } else { "false" };
+    if !value.starts_with(old) {
+        return Ok(None);
+    }
+
+    let value_start = content.len() - value.len();
+    let mut updated = String::with_capacity(content.len() + 1);
+    updated.push_str(&content[..value_start]);
+    updated.push_str(if enabled { "true" } else { "false" });
+    updated.push_str(&value[old.len()..]);
+
+    let partial = installer::sibling_temp_path(path, "partial");
+    fs::write(&partial, &updated)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    fs::rename(&partial, path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(Some(FileAction::Modified))
+}
+
+// Sets enabledPlugins["name@marketplace"] in a Claude Code settings file
+pub fn set_plugin_enabled(settings_path: &Path, id: &str, enabled: bool) -> Result<TouchedFile, String> {
+    let key = format!("enabledPlugins.{}", id);
+    if let Some(action) = flip_in_place(settings_path, id, enabled)? {
+        return Ok(TouchedFile {
+            path: clients::path_to_string(settings_path)?,
+            action,
+            keys: if action == FileAction::Unchanged { Vec::new() } else { vec![key] },
+        });
+    }
+
+    let id = id.to_string();
+    update_json(settings_path, vec![key], move |settings| {
+        let plugins = settings.entry("enabledPlugins")
+            .or_insert_with(|| Value::Object(Default::default()))
+            .as_object_mut()
+            .ok_or_else(|| "enabledPlugins is not a JSON object".to_string())?;
+        plugins.insert(id, Value::Bool(enabled));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-{}", std::process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("settings.json")
+    }
+
+    #[test]
+    fn update_json_keeps_unrelated_keys() {
+        let path = temp_file("update-json");
+        fs::write(&path, "{\n    \"model\": \"opus\",\n    \"env\": {\"A\": \"1\"},\n    \"enabledPlugins\": {\"a@m\": true}\n}\n").unwrap();
+
+        let touched = update_json(&path, vec!["enabledPlugins.b@m".to_string()], |root| {
+            root.get_mut("enabledPlugins")
+                .and_then(Value::as_object_mut)
+                .ok_or("no enabledPlugins")?
+                .insert("b@m".to_string(), Value::Bool(true));
+            Ok(())
+        }).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        assert_eq!(touched.action, FileAction::Modified);
+        assert_eq!(touched.keys, ["enabledPlugins.b@m"]);
+        assert_eq!(
+            serde_json::from_str::<Value>(&content).unwrap(),
+            json!({"model": "opus", "env": {"A": "1"}, "enabledPlugins": {"a@m": true, "b@m": true}}),
+        );
+        assert!(content.starts_with("{\n    \"model\""));
+        assert!(content.ends_with("}\n"));
+    }
+
+    #[test]
+    fn update_json_leaves_unchanged_files_alone() {
+        let path = temp_file("update-json-unchanged");
+        let original = "{\"model\":\"opus\"}";
+        fs::write(&path, original).unwrap();
+
+        let touched = update_json(&path, vec!["model".to_string()], |root| {
+            root.insert("model".to_string(), Value::from("opus"));
+            Ok(())
+        }).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        assert_eq!(touched.action, FileAction::Unchanged);
+        assert!(touched.keys.is_empty());
+        assert_eq!(content, original);
+    }
+
+    fn set_enabled(name: &str, original: &str, id: &str, enabled: bool) -> (Option<FileAction>, TouchedFile, String) {
+        let path = temp_file(name);
+        fs::write(&path, original).unwrap();
+        let flipped = flip_in_place(&path, id, enabled).unwrap();
+        fs::write(&path, original).unwrap();
+        let touched = set_plugin_enabled(&path, id, enabled).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        (flipped, touched, content)
+    }
+
+    #[test]
+    fn flip_in_place_keeps_hand_written_formatting() {
+        let original = "{\n  \"enabledPlugins\" : {\n    \"a@m\" :  true ,\"b@m\":false\n  }\n}\n";
+        let (flipped, touched, content) = set_enabled("flip-format", original, "a@m", false);
+        assert_eq!(flipped, Some(FileAction::Modified));
+        assert_eq!(touched.keys, ["enabledPlugins.a@m"]);
+        assert_eq!(content, original.replace("true", "false"));
+    }
+
+    #[test]
+    fn flip_in_place_leaves_equal_values_alone() {
+        let original = "{\"enabledPlugins\": {\"a@m\": true}}";
+        let (flipped, touched, content) = set_enabled("flip-equal", original, "a@m", true);
+        assert_eq!(flipped, Some(FileAction::Unchanged));
+        assert_eq!(touched.action, FileAction::Unchanged);
+        assert!(touched.keys.is_empty());
+        assert_eq!(content, original);
+    }
+
+    #[test]
+    fn flip_in_place_gives_way_when_the_id_appears_twice() {
+        let original = "{\"notes\": \"a@m\", \"enabledPlugins\": {\"a@m\": true}}";
+        let (flipped, touched, content) = set_enabled("flip-twice", original, "a@m", false);
+        assert_eq!(flipped, None);
+        assert_eq!(touched.action, FileAction::Modified);
+        assert_eq!(
+            serde_json::from_str::<Value>(&content).unwrap(),
+            json!({"notes": "a@m", "enabledPlugins": {"a@m": false}}),
+        );
+    }
+
+    #[test]
+    fn flip_in_place_matches_the_escaped_id() {
+        let original = "{\"enabledPlugins\": {\"a\\\"b@m\": false}}";
+        let (flipped, _, content) = set_enabled("flip-escaped", original, "a\"b@m", true);
+        assert_eq!(flipped, Some(FileAction::Modified));
+        assert_eq!(content, "{\"enabledPlugins\": {\"a\\\"b@m\": true}}");
+
+        // The same key spelled with a different escape is only found by the JSON rewrite
+        let original = "{\"enabledPlugins\": {\"a\\u0040m\": true}}";
+        let (flipped, _, content) = set_enabled("flip-unicode-escape", original, "a@m", false);
+        assert_eq!(flipped, None);
+        assert_eq!(serde_json::from_str::<Value>(&content).unwrap(), json!({"enabledPlugins": {"a@m": false}}));
+    }
+
+    #[test]
+    fn flip_in_place_only_reads_top_level_enabled_plugins() {
+        let original = "{\"profile\": {\"enabledPlugins\": {\"a@m\": true}}}";
+        let (flipped, _, content) = set_enabled("flip-nested", original, "a@m", false);
+        assert_eq!(flipped, None);
+        assert_eq!(
+            serde_json::from_str::<Value>(&content).unwrap(),
+            json!({"profile": {"enabledPlugins": {"a@m": true}}, "enabledPlugins": {"a@m": false}}),
+        );
+
+        let (flipped, touched, content) = set_enabled("flip-absent", "{\"model\": \"opus\"}", "a@m", true);
+        assert_eq!(flipped, None);
+        assert_eq!(touched.action, FileAction::Modified);
+        assert_eq!(
+            serde_json::from_str::<Value>(&content).unwrap(),
+            json!({"model": "opus", "enabledPlugins": {"a@m": true}}),
+        );
+    }
+}
+
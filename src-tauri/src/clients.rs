@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Client {
+    Claude,
+    ClaudeCode,
+    Cursor,
+    Vscode,
+    Codex,
+    Amp,
+    Opencode,
+    Goose,
+    Letta,
+    Github,
+}
+
+pub const ALL_CLIENTS: [Client; 10] = [
+    Client::Claude,
+    Client::ClaudeCode,
+    Client::Cursor,
+    Client::Vscode,
+    Client::Codex,
+    Client::Amp,
+    Client::Opencode,
+    Client::Goose,
+    Client::Letta,
+    Client::Github,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillType {
+    Personal,
+    Project,
+}
+
+impl Client {
+    // Mirrors CLIENT_PERSONAL_SKILL_PATHS in src/types/index.ts, relative to the home directory
+    pub fn personal_skill_dir(&self) -> &'static str {
+        match self {
+            Client::Claude | Client::ClaudeCode => ".claude/skills",
+            Client::Cursor => ".cursor/skills",
+            Client::Vscode => ".vscode/skills",
+            Client::Codex => ".codex/skills",
+            Client::Amp => ".amp/skills",
+            Client::Opencode => ".opencode/skills",
+            Client::Goose => ".goose/skills",
+            Client::Letta => ".letta/skills",
+            Client::Github => ".github/skills",
+        }
+    }
+
+    // Mirrors CLIENT_LOCAL_SKILL_PATHS, relative to the project root. The Claude
+    // desktop app has no project scope, so it always resolves to the personal root.
+    pub fn project_skill_dir(&self) -> Option<&'static str> {
+        match self {
+            Client::Claude => None,
+            Client::ClaudeCode => Some(".claude/skills"),
+            Client::Cursor => Some(".cursor/skills"),
+            Client::Vscode => Some(".vscode/skills"),
+            Client::Codex => Some(".codex/skills"),
+            Client::Amp => Some(".amp/skills"),
+            Client::Opencode => Some(".opencode/skills"),
+            Client::Goose => Some(".goose/skills"),
+            Client::Letta => Some(".letta/skills"),
+            Client::Github => Some(".github/skills"),
+        }
+    }
+
+    // Files and folders (relative to the project root) whose presence means the project is
+    // set up for this client. `.github` alone is too common to count, and AGENTS.md is
+    // read by several clients, so only Codex claims it.
+    pub fn project_markers(&self) -> &'static [&'static str] {
+        match self {
+            Client::Claude => &[],
+            Client::ClaudeCode => &[".claude", "CLAUDE.md", ".mcp.json"],
+            Client::Cursor => &[".cursor", ".cursorrules"],
+            Client::Vscode => &[".vscode"],
+            Client::Codex => &[".codex", "AGENTS.md"],
+            Client::Amp => &[".amp"],
+            Client::Opencode => &[".opencode", "opencode.json"],
+            Client::Goose => &[".goose", ".goosehints"],
+            Client::Letta => &[".letta"],
+            Client::Github => &[
+                ".github/copilot-instructions.md",
+                ".github/skills",
+                ".github/agents",
+                ".github/prompts",
+                ".github/instructions",
+            ],
+        }
+    }
+
+    // Which copy a client loads when a skill name exists in both its personal and project
+    // directories. Claude Code documents personal skills overriding project ones; the
+    // other clients prefer the project copy.
+    pub fn preferred_skill_type(&self) -> SkillType {
+        match self {
+            Client::Claude | Client::ClaudeCode => SkillType::Personal,
+            _ => SkillType::Project,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Client::Claude => "claude",
+            Client::ClaudeCode => "claude-code",
+            Client::Cursor => "cursor",
+            Client::Vscode => "vscode",
+            Client::Codex => "codex",
+            Client::Amp => "amp",
+            Client::Opencode => "opencode",
+            Client::Goose => "goose",
+            Client::Letta => "letta",
+            Client::Github => "github",
+        }
+    }
+}
+
+pub fn home_dir() -> Result<PathBuf, String> {
+    dirs::home_dir().ok_or_else(|| "Could not find home directory".to_string())
+}
+
+pub fn expand_home(path: &str) -> Result<PathBuf, String> {
+    if path == "~" {
+        return home_dir();
+    }
+    if let Some(rest) = path.strip_prefix("~/") {
+        return Ok(home_dir()?.join(rest));
+    }
+    Ok(PathBuf::from(path))
+}
+
+pub fn skill_root(client: Client, skill_type: SkillType, project_path: Option<&str>) -> Result<PathBuf, String> {
+    match (skill_type, client.project_skill_dir()) {
+        (SkillType::Project, Some(dir)) => {
+            let project = project_path
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| "A project path is required for project skills".to_string())?;
+            Ok(expand_home(project)?.join(dir))
+        }
+        _ => Ok(home_dir()?.join(client.personal_skill_dir())),
+    }
+}
+
+pub fn validate_skill_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains('/')
+        || name.contains('\\')
+        || Path::new(name).is_absolute()
+    {
+        return Err(format!("Invalid skill name: {}", name));
+    }
+    Ok(())
+}
+
+pub fn path_to_string(path: &Path) -> Result<String, String> {
+    path.to_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Invalid file path".to_string())
+}
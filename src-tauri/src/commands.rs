@@ -4,6 +4,8 @@ use std::fs;
 use std::io::Write;
 use serde::Serialize;
 
+use crate::remote;
+
 #[derive(Serialize)]
 pub struct TerminalApp {
     pub name: String,
@@ -384,10 +386,17 @@ pub async fn open_in_explorer(path: String) -> Result<(), String> {
     Ok(())
 }
 
+pub fn resolve_download_dir(download_path: Option<String>) -> Result<PathBuf, String> {
+    match download_path {
+        Some(path) if !path.is_empty() => Ok(PathBuf::from(path)),
+        _ => dirs::download_dir()
+            .ok_or_else(|| "Could not find download directory".to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn get_default_download_path() -> Result<String, String> {
-    let download_dir = dirs::download_dir()
-        .ok_or_else(|| "Could not find download directory".to_string())?;
+    let download_dir = resolve_download_dir(None)?;
     
     download_dir
         .to_str()
@@ -398,17 +407,7 @@ pub async fn get_default_download_path() -> Result<String, String> {
 #[tauri::command]
 pub async fn download_skill(url: String, filename: String, download_path: Option<String>) -> Result<String, String> {
     // Get the target directory
-    let target_dir: PathBuf = if let Some(path) = download_path {
-        if path.is_empty() {
-            dirs::download_dir()
-                .ok_or_else(|| "Could not find download directory".to_string())?
-        } else {
-            PathBuf::from(path)
-        }
-    } else {
-        dirs::download_dir()
-            .ok_or_else(|| "Could not find download directory".to_string())?
-    };
+    let target_dir = resolve_download_dir(download_path)?;
     
     // Ensure directory exists
     fs::create_dir_all(&target_dir)
@@ -418,15 +417,7 @@ pub async fn download_skill(url: String, filename: String, download_path: Option
     let file_path = target_dir.join(&filename);
     
     // Download the file using reqwest (blocking)
-    let response = reqwest::blocking::get(&url)
-        .map_err(|e| format!("Failed to download: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("Download failed with status: {}", response.status()));
-    }
-    
-    let bytes = response.bytes()
-        .map_err(|e| format!("Failed to read response: {}", e))?;
+    let bytes = remote::fetch_bytes(&url)?;
     
     // Write to file
     let mut file = fs::File::create(&file_path)
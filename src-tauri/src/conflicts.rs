@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+
+use crate::clients::{self, Client, SkillType};
+use crate::hashing;
+use crate::inventory::{self, DiscoveredSkill};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictKind {
+    // At least one client sees more than one copy and only loads one of them
+    Shadowed,
+    // Different clients each have their own copy under the same name
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillCopy {
+    pub path: String,
+    pub clients: Vec<Client>,
+    pub skill_type: SkillType,
+    pub hash: Option<String>,
+    // Clients that load this copy, and clients that ignore it in favour of another one
+    pub active_for: Vec<Client>,
+    pub shadowed_for: Vec<Client>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillConflict {
+    pub name: String,
+    pub kind: ConflictKind,
+    pub identical: bool,
+    pub copies: Vec<SkillCopy>,
+}
+
+fn analyze(name: &str, skills: &[&DiscoveredSkill]) -> SkillConflict {
+    let mut copies: Vec<SkillCopy> = skills.iter()
+        .map(|skill| SkillCopy {
+            path: skill.path.to_string_lossy().to_string(),
+            clients: skill.root.clients.clone(),
+            skill_type: skill.root.skill_type,
+            hash: hashing::dir_hash(&skill.path).ok(),
+            active_for: Vec::new(),
+            shadowed_for: Vec::new(),
+        })
+        .collect();
+
+    let mut shadowed = false;
+    let mut clients: Vec<Client> = Vec::new();
+    for client in copies.iter().flat_map(|c| c.clients.iter()) {
+        if !clients.contains(client) {
+            clients.push(*client);
+        }
+    }
+    for client in clients {
+        let visible: Vec<usize> = (0..copies.len())
+            .filter(|&i| copies[i].clients.contains(&client))
+            .collect();
+        if visible.len() > 1 {
+            shadowed = true;
+        }
+
+        let preferred = client.preferred_skill_type();
+        let winner = visible.iter()
+            .copied()
+            .find(|&i| copies[i].skill_type == preferred)
+            .or_else(|| visible.first().copied());
+        for i in visible {
+            if Some(i) == winner {
+                copies[i].active_for.push(client);
+            } else {
+                copies[i].shadowed_for.push(client);
+            }
+        }
+    }
+
+    // Unreadable copies can't be compared, so they never count as identical
+    let identical = copies.iter().all(|c| c.hash.is_some() && c.hash == copies[0].hash);
+
+    SkillConflict {
+        name: name.to_string(),
+        kind: if shadowed { ConflictKind::Shadowed } else { ConflictKind::Duplicate },
+        identical,
+        copies,
+    }
+}
+
+pub fn find_conflicts(project_paths: &[String]) -> Vec<SkillConflict> {
+    let discovered = inventory::discover_skills(project_paths);
+
+    let mut by_name: BTreeMap<&str, Vec<&DiscoveredSkill>> = BTreeMap::new();
+    for skill in &discovered {
+        by_name.entry(skill.name.as_str()).or_default().push(skill);
+    }
+
+    let mut conflicts: Vec<SkillConflict> = by_name.iter()
+        .filter(|(_, skills)| skills.len() > 1)
+        .map(|(name, skills)| analyze(name, skills))
+        .collect();
+
+    // Shadowed copies that differ are the ones that cause surprises, so they come first
+    conflicts.sort_by_key(|c| (c.kind != ConflictKind::Shadowed, c.identical));
+    conflicts
+}
+
+#[tauri::command]
+pub async fn find_skill_conflicts(project_path: Option<String>) -> Result<Vec<SkillConflict>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let project_paths = match project_path.filter(|p| !p.is_empty()) {
+            Some(path) => vec![clients::path_to_string(&clients::expand_home(&path)?)?],
+            None => Vec::new(),
+        };
+        Ok(find_conflicts(&project_paths))
+    })
+    .await
+    .map_err(|e| format!("Conflict check failed: {}", e))?
+}
@@ -0,0 +1,405 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::baseline::{self, Baseline};
+use crate::clients;
+use crate::hashing;
+use crate::remote::{self, Conditional, SKILL_FILE};
+
+// Lines of unchanged text kept around each change
+const CONTEXT_LINES: usize = 3;
+// Past this many LCS cells (old lines x new lines) the file is shown as fully replaced
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub kind: LineKind,
+    pub text: String,
+    // 1-based line numbers in the local and upstream files
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    // Last line of a file that doesn't end in a newline, like diff's "\ No newline at end of file"
+    pub missing_newline: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileChange {
+    Unchanged,
+    Modified,
+    AddedUpstream,
+    RemovedUpstream,
+    // Exists locally, not upstream, and wasn't part of what Skiller installed
+    LocalOnly,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiff {
+    pub path: String,
+    pub change: FileChange,
+    // Whether the local file differs from what was installed; None when no install
+    // baseline was recorded (skills installed before baselines existed, or by hand)
+    pub locally_modified: Option<bool>,
+    // Edited locally and changed upstream
+    pub conflict: bool,
+    pub binary: bool,
+    pub hunks: Vec<DiffHunk>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDiff {
+    pub path: String,
+    pub raw_file_url: String,
+    pub baseline_recorded_at: Option<String>,
+    pub files: Vec<FileDiff>,
+    pub upstream_changed: bool,
+    pub locally_modified: Option<bool>,
+    // Updating replaces the whole directory, so any local edit would be lost
+    pub overwrites_local_changes: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+fn diff_ops(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<Op> = (0..prefix).map(|i| Op::Equal(i, i)).collect();
+
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_DIFF_CELLS {
+        ops.extend((0..old_mid.len()).map(|i| Op::Delete(prefix + i)));
+        ops.extend((0..new_mid.len()).map(|j| Op::Insert(prefix + j)));
+    } else {
+        // lcs[i][j] = length of the longest common subsequence of old_mid[i..] and new_mid[j..]
+        let (n, m) = (old_mid.len(), new_mid.len());
+        let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i * (m + 1) + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * (m + 1) + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && old_mid[i] == new_mid[j] {
+                ops.push(Op::Equal(prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1]) {
+                ops.push(Op::Delete(prefix + i));
+                i += 1;
+            } else {
+                ops.push(Op::Insert(prefix + j));
+                j += 1;
+            }
+        }
+    }
+
+    let old_tail = old.len() - suffix;
+    let new_tail = new.len() - suffix;
+    ops.extend((0..suffix).map(|k| Op::Equal(old_tail + k, new_tail + k)));
+    ops
+}
+
+fn to_line(op: Op, old: &[&str], new: &[&str]) -> DiffLine {
+    let (kind, line, old_line, new_line) = match op {
+        Op::Equal(i, j) => (LineKind::Context, old[i], Some(i + 1), Some(j + 1)),
+        Op::Delete(i) => (LineKind::Removed, old[i], Some(i + 1), None),
+        Op::Insert(j) => (LineKind::Added, new[j], None, Some(j + 1)),
+    };
+    let text = line.strip_suffix('\n').map(|l| l.strip_suffix('\r').unwrap_or(l));
+    DiffLine {
+        kind,
+        text: text.unwrap_or(line).to_string(),
+        old_line,
+        new_line,
+        missing_newline: text.is_none(),
+    }
+}
+
+// Unified-diff style hunks of `new` against `old`. Lines keep their terminators while being
+// compared, so a file that only gained or lost its final newline still shows a change.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffHunk> {
+    let old: Vec<&str> = old.split_inclusive('\n').collect();
+    let new: Vec<&str> = new.split_inclusive('\n').collect();
+    let ops = diff_ops(&old, &new);
+
+    let changes: Vec<usize> = ops.iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, Op::Equal(..)))
+        .map(|(index, _)| index)
+        .collect();
+
+    // Group changes whose context windows touch into one hunk
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for index in changes {
+        let start = index.saturating_sub(CONTEXT_LINES);
+        let end = (index + CONTEXT_LINES + 1).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    ranges.into_iter()
+        .map(|(start, end)| {
+            let lines: Vec<DiffLine> = ops[start..end].iter().map(|&op| to_line(op, &old, &new)).collect();
+            let old_lines = lines.iter().filter(|l| l.kind != LineKind::Added).count();
+            let new_lines = lines.iter().filter(|l| l.kind != LineKind::Removed).count();
+            // Like `diff -u`, an empty side starts at the line before the hunk
+            let old_start = lines.iter().find_map(|l| l.old_line)
+                .unwrap_or_else(|| ops[..start].iter().filter(|op| !matches!(op, Op::Insert(_))).count());
+            let new_start = lines.iter().find_map(|l| l.new_line)
+                .unwrap_or_else(|| ops[..start].iter().filter(|op| !matches!(op, Op::Delete(_))).count());
+            DiffHunk { old_start, old_lines, new_start, new_lines, lines }
+        })
+        .collect()
+}
+
+// Every upstream file that can be found: the listed ones plus known files (local or from
+// the baseline) probed by relative URL, since listing only works for GitHub
+fn fetch_upstream(raw_file_url: &str, known: &BTreeSet<String>) -> Result<BTreeMap<String, Vec<u8>>, String> {
+    let mut upstream = BTreeMap::new();
+    // Hidden files are never part of the local side, so they aren't compared upstream either
+    for remote_file in remote::list_skill_files(raw_file_url)?.into_iter().filter(|f| hashing::is_tracked(&f.path)) {
+        upstream.insert(remote_file.path.clone(), remote::fetch_bytes(&remote_file.url)?);
+    }
+
+    let unlisted: Vec<&String> = known.iter().filter(|r| !upstream.contains_key(*r)).collect();
+    for relative in unlisted {
+        let url = remote::sibling_url(raw_file_url, relative)?;
+        if let Conditional::Modified { bytes, .. } = remote::fetch_conditional(&url, None, None)? {
+            upstream.insert(relative.clone(), bytes);
+        }
+    }
+
+    if !upstream.contains_key(SKILL_FILE) {
+        return Err(format!("{} no longer exists upstream", SKILL_FILE));
+    }
+    Ok(upstream)
+}
+
+// Missing files diff as empty text; None means the bytes aren't UTF-8
+fn as_text(bytes: Option<&[u8]>) -> Option<&str> {
+    match bytes {
+        Some(bytes) => std::str::from_utf8(bytes).ok(),
+        None => Some(""),
+    }
+}
+
+fn diff_file(
+    relative: &str,
+    local: Option<&[u8]>,
+    upstream: Option<&[u8]>,
+    baseline: Option<&Baseline>,
+) -> FileDiff {
+    let local_hash = local.map(hashing::sha256_hex);
+    let upstream_hash = upstream.map(hashing::sha256_hex);
+    let installed_hash = baseline.and_then(|b| b.files.get(relative));
+
+    let change = match (local, upstream) {
+        (Some(_), Some(_)) if local_hash == upstream_hash => FileChange::Unchanged,
+        (Some(_), Some(_)) => FileChange::Modified,
+        (None, _) => FileChange::AddedUpstream,
+        (Some(_), None) if installed_hash.is_some() => FileChange::RemovedUpstream,
+        (Some(_), None) => FileChange::LocalOnly,
+    };
+
+    let locally_modified = baseline.map(|_| local_hash.as_ref() != installed_hash);
+    let upstream_changed = upstream_hash.as_ref() != installed_hash;
+    let conflict = locally_modified == Some(true) && upstream_changed && change != FileChange::Unchanged;
+
+    let (binary, hunks) = match (change, as_text(local), as_text(upstream)) {
+        (FileChange::Unchanged, _, _) => (false, Vec::new()),
+        (_, Some(old), Some(new)) => (false, diff_lines(old, new)),
+        _ => (true, Vec::new()),
+    };
+
+    FileDiff {
+        path: relative.to_string(),
+        change,
+        locally_modified,
+        conflict,
+        binary,
+        hunks,
+    }
+}
+
+pub fn diff_skill(dir: &Path, raw_file_url: &str, baseline: Option<&Baseline>) -> Result<SkillDiff, String> {
+    if !dir.join(SKILL_FILE).is_file() {
+        return Err(format!("{} is not an installed skill", dir.display()));
+    }
+
+    let mut local = BTreeMap::new();
+    for relative in hashing::list_files(dir)? {
+        let bytes = fs::read(dir.join(&relative))
+            .map_err(|e| format!("Failed to read {}: {}", relative, e))?;
+        local.insert(relative, bytes);
+    }
+    // Files deleted locally still count as local changes, and are probed upstream like the rest
+    let mut known_paths: BTreeSet<String> = local.keys().cloned().collect();
+    if let Some(baseline) = baseline {
+        known_paths.extend(baseline.files.keys().cloned());
+    }
+    let upstream = fetch_upstream(raw_file_url, &known_paths)?;
+
+    let mut paths = known_paths;
+    paths.extend(upstream.keys().cloned());
+
+    let files: Vec<FileDiff> = paths.iter()
+        .filter(|p| local.contains_key(*p) || upstream.contains_key(*p))
+        .map(|p| diff_file(p, local.get(p).map(Vec::as_slice), upstream.get(p).map(Vec::as_slice), baseline))
+        .collect();
+
+    let deleted_locally = baseline.map(|b| b.files.keys().any(|p| !local.contains_key(p))).unwrap_or(false);
+    let locally_modified = baseline.map(|_| deleted_locally || files.iter().any(|f| f.locally_modified == Some(true)));
+    // With a baseline, upstream changed when it differs from what was installed; without one,
+    // any difference from the local copy is the best available guess
+    let upstream_changed = match baseline {
+        Some(baseline) => paths.iter().any(|p| {
+            upstream.get(p).map(|bytes| hashing::sha256_hex(bytes)).as_ref() != baseline.files.get(p)
+        }),
+        None => files.iter().any(|f| f.change != FileChange::Unchanged && f.change != FileChange::LocalOnly),
+    };
+
+    Ok(SkillDiff {
+        path: clients::path_to_string(dir)?,
+        raw_file_url: raw_file_url.to_string(),
+        baseline_recorded_at: baseline.map(|b| b.recorded_at.clone()),
+        upstream_changed,
+        overwrites_local_changes: locally_modified == Some(true)
+            || files.iter().any(|f| f.change == FileChange::LocalOnly),
+        locally_modified,
+        files,
+    })
+}
+
+#[tauri::command]
+pub async fn diff_skill_update(app: AppHandle, path: String, raw_file_url: String) -> Result<SkillDiff, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = clients::expand_home(&path)?;
+        let baseline = baseline::load_baseline(&app, &dir)?;
+        diff_skill(&dir, &raw_file_url, baseline.as_ref())
+    })
+    .await
+    .map_err(|e| format!("Diff failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(hunks: &[DiffHunk]) -> Vec<String> {
+        hunks.iter()
+            .flat_map(|hunk| {
+                let header = format!("@@ -{},{} +{},{} @@", hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines);
+                std::iter::once(header).chain(hunk.lines.iter().map(|line| {
+                    let sign = match line.kind {
+                        LineKind::Context => ' ',
+                        LineKind::Added => '+',
+                        LineKind::Removed => '-',
+                    };
+                    let marker = if line.missing_newline { " \\" } else { "" };
+                    format!("{}{}{}", sign, line.text, marker)
+                }))
+            })
+            .collect()
+    }
+
+    // Lines "1" to "20", with the given line numbers replaced
+    fn numbered(changed: &[(usize, &str)]) -> String {
+        (1..=20)
+            .map(|n| match changed.iter().find(|(line, _)| *line == n) {
+                Some((_, text)) => format!("{}\n", text),
+                None => format!("{}\n", n),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identical_texts_have_no_hunks() {
+        assert!(diff_lines("a\nb\n", "a\nb\n").is_empty());
+        assert!(diff_lines("", "").is_empty());
+    }
+
+    #[test]
+    fn an_empty_side_diffs_as_a_whole_file_change() {
+        assert_eq!(render(&diff_lines("", "a\nb\n")), ["@@ -0,0 +1,2 @@", "+a", "+b"]);
+        assert_eq!(render(&diff_lines("a\nb\n", "")), ["@@ -1,2 +0,0 @@", "-a", "-b"]);
+    }
+
+    #[test]
+    fn pure_inserts_and_deletes_keep_their_context() {
+        assert_eq!(
+            render(&diff_lines("a\nb\nc\n", "a\nb\nx\nc\n")),
+            ["@@ -1,3 +1,4 @@", " a", " b", "+x", " c"],
+        );
+        assert_eq!(
+            render(&diff_lines("a\nb\nx\nc\n", "a\nb\nc\n")),
+            ["@@ -1,4 +1,3 @@", " a", " b", "-x", " c"],
+        );
+    }
+
+    #[test]
+    fn nearby_changes_share_a_hunk_and_distant_ones_do_not() {
+        let old = numbered(&[]);
+        let hunks = diff_lines(&old, &numbered(&[(5, "five"), (10, "ten")]));
+        assert_eq!(hunks.len(), 1);
+        assert_eq!((hunks[0].old_start, hunks[0].old_lines), (2, 12));
+
+        let hunks = diff_lines(&old, &numbered(&[(2, "two"), (19, "nineteen")]));
+        assert_eq!(hunks.len(), 2);
+        assert_eq!((hunks[0].old_start, hunks[0].old_lines), (1, 5));
+        assert_eq!((hunks[1].old_start, hunks[1].old_lines), (16, 5));
+    }
+
+    #[test]
+    fn a_missing_final_newline_is_a_change() {
+        assert_eq!(
+            render(&diff_lines("a\nb", "a\nb\n")),
+            ["@@ -1,2 +1,2 @@", " a", "-b \\", "+b"],
+        );
+        assert_eq!(
+            render(&diff_lines("a\r\nb\r\n", "a\r\nc\r\n")),
+            ["@@ -1,2 +1,2 @@", " a", "-b", "+c"],
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use sha2::{Digest, Sha256};
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+// Hidden names that never belong to a skill or plugin, even when dotfiles are kept
+pub fn is_junk_name(name: &str) -> bool {
+    name == ".git" || name == ".DS_Store" || name == "__MACOSX" || name.contains(".skiller-")
+}
+
+// Whether list_files (and so file_hashes and dir_hash) covers a relative path like "scripts/run.sh".
+// Listings from elsewhere are filtered with this so both sides compare the same files.
+pub fn is_tracked(relative: &str) -> bool {
+    relative.split(['/', '\\'])
+        .all(|part| !is_junk_name(part) && !part.starts_with('.'))
+}
+
+fn collect_files(base: &Path, dir: &Path, include_hidden: bool, files: &mut Vec<String>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_junk_name(&name) || (name.starts_with('.') && !include_hidden) {
+            continue;
+        }
+        // Links are skipped rather than followed, so one pointing at ~/.ssh or elsewhere
+        // outside the source can't pull those files into a skill or bundle
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_files(base, &path, include_hidden, files)?;
+        } else if file_type.is_file() {
+            if let Ok(relative) = path.strip_prefix(base) {
+                files.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Relative paths ("scripts/run.sh") of every non-hidden file in a skill directory, sorted
+pub fn list_files(dir: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, false, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+// Plugins keep their manifest in .claude-plugin/ and MCP config in .mcp.json, so dotfiles
+// are part of them; only VCS and OS metadata is left out
+pub fn list_files_with_hidden(dir: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, true, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+pub fn file_hashes(dir: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut hashes = BTreeMap::new();
+    for relative in list_files(dir)? {
+        let bytes = fs::read(dir.join(&relative))
+            .map_err(|e| format!("Failed to read {}: {}", relative, e))?;
+        hashes.insert(relative, sha256_hex(&bytes));
+    }
+    Ok(hashes)
+}
+
+// A single digest for the whole directory, stable across platforms and file ordering
+pub fn combined_hash(hashes: &BTreeMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    for (path, hash) in hashes {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(hash.as_bytes());
+        hasher.update([b'\n']);
+    }
+    hasher.finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub fn dir_hash(dir: &Path) -> Result<String, String> {
+    Ok(combined_hash(&file_hashes(dir)?))
+}
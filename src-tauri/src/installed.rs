@@ -0,0 +1,120 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::clients::{Client, SkillType};
+
+// Same store file and key the frontend uses in src/hooks/useInstalled.ts
+const INSTALLED_STORE: &str = "installed.json";
+const INSTALLED_KEY: &str = "installed";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledItem {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    pub installed_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<Client>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_type: Option<SkillType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+// Same format as JavaScript's `new Date().toISOString()`, which the frontend writes for `installedAt`
+pub fn timestamp_now() -> String {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = elapsed.as_secs() as i64;
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+pub fn load_installed(app: &AppHandle) -> Result<Vec<InstalledItem>, String> {
+    let store = app.store(INSTALLED_STORE)
+        .map_err(|e| format!("Failed to open installed store: {}", e))?;
+
+    match store.get(INSTALLED_KEY) {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse installed store: {}", e)),
+        None => Ok(Vec::new()),
+    }
+}
+
+pub fn save_installed(app: &AppHandle, items: &[InstalledItem]) -> Result<(), String> {
+    let store = app.store(INSTALLED_STORE)
+        .map_err(|e| format!("Failed to open installed store: {}", e))?;
+    let value = serde_json::to_value(items)
+        .map_err(|e| format!("Failed to serialize installed store: {}", e))?;
+
+    store.set(INSTALLED_KEY, value);
+    store.save()
+        .map_err(|e| format!("Failed to save installed store: {}", e))
+}
+
+// Returns whether an entry was removed
+pub fn remove_installed(app: &AppHandle, id: &str, kind: &str) -> Result<bool, String> {
+    let mut items = load_installed(app)?;
+    let before = items.len();
+    items.retain(|i| !(i.id == id && i.kind == kind));
+
+    if items.len() == before {
+        return Ok(false);
+    }
+    save_installed(app, &items)?;
+    Ok(true)
+}
+
+// Points an entry recorded for one client at another client's copy of the same skill;
+// returns whether an entry was changed
+pub fn retarget_installed(app: &AppHandle, id: &str, kind: &str, from: Client, to: Client, path: &str) -> Result<bool, String> {
+    let mut items = load_installed(app)?;
+    let Some(item) = items.iter_mut().find(|i| i.id == id && i.kind == kind && i.client == Some(from)) else {
+        return Ok(false);
+    };
+
+    item.client = Some(to);
+    if item.path.is_some() {
+        item.path = Some(path.to_string());
+    }
+    save_installed(app, &items)?;
+    Ok(true)
+}
+
+// Mirrors addInstalled in useInstalled.ts: an existing entry with the same id and type wins
+pub fn add_installed(app: &AppHandle, item: InstalledItem) -> Result<bool, String> {
+    let mut items = load_installed(app)?;
+    if items.iter().any(|i| i.id == item.id && i.kind == item.kind) {
+        return Ok(false);
+    }
+
+    items.push(item);
+    save_installed(app, &items)?;
+    Ok(true)
+}
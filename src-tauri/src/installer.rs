@@ -0,0 +1,601 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::backups::{BackupArea, BackupReason};
+use crate::baseline;
+use crate::clients::{self, Client, SkillType, ALL_CLIENTS};
+use crate::hashing;
+use crate::installed;
+use crate::lockfile::{self, LockSource};
+use crate::remote::{self, SKILL_FILE};
+use crate::shared_store;
+use crate::skill_md;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallMode {
+    #[default]
+    Copy,
+    Link,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallTarget {
+    pub client: Client,
+    pub skill_type: SkillType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallStatus {
+    Installed,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientInstallResult {
+    pub client: Client,
+    pub skill_type: SkillType,
+    pub status: InstallStatus,
+    pub path: Option<String>,
+    pub reason: Option<String>,
+    // Bookkeeping that failed after the files were written; the install itself went through
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallResult {
+    pub path: String,
+    pub removed_files: bool,
+    pub removed_record: bool,
+}
+
+pub struct SkillFile {
+    pub path: PathBuf,
+    pub contents: Vec<u8>,
+    // Unix permission bits from the source, when it has them (scripts need their exec bit)
+    pub mode: Option<u32>,
+}
+
+// Rejects absolute paths, drive letters and `..` so remote listings and archives can't escape
+// the skill directory. Backslashes count as separators too, since archives made on Windows use them.
+pub fn safe_relative_path(path: &str) -> Option<PathBuf> {
+    if path.starts_with(['/', '\\']) {
+        return None;
+    }
+    let mut result = PathBuf::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => return None,
+            // "C:" and NTFS alternate data streams
+            _ if part.contains(':') => return None,
+            _ => result.push(part),
+        }
+    }
+    if result.as_os_str().is_empty() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+// Hidden sibling used for staging and removal, so it lives on the same filesystem as `target`
+pub fn sibling_temp_path(target: &Path, tag: &str) -> PathBuf {
+    let name = target.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    target.with_file_name(format!(".{}.skiller-{}-{}-{}", name, tag, std::process::id(), nanos))
+}
+
+pub fn remove_path(path: &Path) -> Result<(), String> {
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let result = if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    result.map_err(|e| format!("Failed to remove {}: {}", path.display(), e))
+}
+
+// `target` must be a direct child of `root` once symlinked ancestors are resolved
+pub fn ensure_within_root(root: &Path, target: &Path) -> Result<(), String> {
+    let canonical_root = root.canonicalize()
+        .map_err(|e| format!("Failed to resolve skill root {}: {}", root.display(), e))?;
+    let canonical_parent = target.parent()
+        .ok_or_else(|| format!("Invalid skill path: {}", target.display()))?
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", target.display(), e))?;
+
+    if canonical_parent != canonical_root {
+        return Err(format!(
+            "Refusing to modify {} because it is outside the skill directory {}",
+            target.display(),
+            root.display()
+        ));
+    }
+    Ok(())
+}
+
+pub fn fetch_skill_files(raw_file_url: &str) -> Result<Vec<SkillFile>, String> {
+    let mut files = Vec::new();
+    for remote_file in remote::list_skill_files(raw_file_url)? {
+        let path = safe_relative_path(&remote_file.path)
+            .ok_or_else(|| format!("Refusing to write outside the skill directory: {}", remote_file.path))?;
+        let contents = remote::fetch_bytes(&remote_file.url)?;
+        files.push(SkillFile { path, contents, mode: None });
+    }
+    Ok(files)
+}
+
+pub fn validate_skill_files(files: &[SkillFile]) -> Result<skill_md::ParsedSkill, String> {
+    let skill_file = files.iter()
+        .find(|f| f.path == Path::new(SKILL_FILE))
+        .ok_or_else(|| format!("Skill has no {}", SKILL_FILE))?;
+    skill_md::ensure_installable(&skill_file.contents)
+}
+
+pub fn write_skill_files(target: &Path, files: &[SkillFile]) -> Result<(), String> {
+    fs::create_dir_all(target)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    for file in files {
+        let file_path = target.join(&file.path);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let mut handle = fs::File::create(&file_path)
+            .map_err(|e| format!("Failed to create file: {}", e))?;
+        handle.write_all(&file.contents)
+            .and_then(|_| handle.sync_all())
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+        if let Some(mode) = file.mode {
+            set_mode(&file_path, mode)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Permission bits worth keeping from a file on disk
+#[cfg(unix)]
+pub fn file_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o777)
+}
+
+#[cfg(not(unix))]
+pub fn file_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+// Only the rwx bits are applied, and the owner always keeps read/write so the skill can be
+// updated or removed later
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode((mode & 0o777) | 0o600))
+        .map_err(|e| format!("Failed to set permissions on {}: {}", path.display(), e))
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> Result<(), String> {
+    Ok(())
+}
+
+// Moves a fully prepared `staging` entry (directory or link) onto `target` with renames.
+// If the swap fails, the previous version is put back.
+pub fn swap_into_place(staging: &Path, target: &Path) -> Result<(), String> {
+    let previous = if fs::symlink_metadata(target).is_ok() {
+        let previous = sibling_temp_path(target, "previous");
+        if let Err(e) = fs::rename(target, &previous) {
+            let _ = remove_path(staging);
+            return Err(format!("Failed to replace existing skill: {}", e));
+        }
+        Some(previous)
+    } else {
+        None
+    };
+
+    if let Err(e) = fs::rename(staging, target) {
+        if let Some(previous) = &previous {
+            let _ = fs::rename(previous, target);
+        }
+        let _ = remove_path(staging);
+        return Err(format!("Failed to move skill into place: {}", e));
+    }
+
+    if let Some(previous) = previous {
+        let _ = remove_path(&previous);
+    }
+
+    Ok(())
+}
+
+pub fn ensure_parent_dir(target: &Path) -> Result<(), String> {
+    let parent = target.parent()
+        .ok_or_else(|| format!("Invalid skill path: {}", target.display()))?;
+    fs::create_dir_all(parent)
+        .map_err(|e| format!("Failed to create directory: {}", e))
+}
+
+// Writes the skill into a hidden staging directory next to `target` and swaps it in,
+// so an interrupted install never leaves a half-written skill behind
+pub fn install_files(target: &Path, files: &[SkillFile]) -> Result<(), String> {
+    ensure_parent_dir(target)?;
+
+    let staging = sibling_temp_path(target, "staging");
+    if let Err(e) = write_skill_files(&staging, files) {
+        let _ = remove_path(&staging);
+        return Err(e);
+    }
+
+    swap_into_place(&staging, target)
+}
+
+// Hidden files are written but never hashed, so they are left out of the comparison
+pub fn matches_installed(target: &Path, files: &[SkillFile]) -> bool {
+    let Ok(existing) = hashing::file_hashes(target) else {
+        return false;
+    };
+    let tracked: Vec<(String, &SkillFile)> = files.iter()
+        .map(|f| (f.path.to_string_lossy().replace('\\', "/"), f))
+        .filter(|(relative, _)| hashing::is_tracked(relative))
+        .collect();
+    existing.len() == tracked.len()
+        && tracked.iter().all(|(relative, f)| existing.get(relative) == Some(&hashing::sha256_hex(&f.contents)))
+}
+
+// Installs already-fetched files into every target, reporting each one separately so
+// a failure in one client doesn't hide what happened in the others. With a `shared`
+// copy, each target becomes a link to it instead of a copy of the files.
+pub fn install_to_targets(
+    files: &[SkillFile],
+    name: &str,
+    targets: &[InstallTarget],
+    project_path: Option<&str>,
+    shared: Option<&Path>,
+    backups: Option<&BackupArea>,
+) -> Vec<ClientInstallResult> {
+    let mut results: Vec<ClientInstallResult> = Vec::new();
+    let mut written: Vec<(PathBuf, Client)> = Vec::new();
+
+    for target in targets {
+        let result = |status, path: Option<&Path>, reason: Option<String>| ClientInstallResult {
+            client: target.client,
+            skill_type: target.skill_type,
+            status,
+            path: path.map(|p| p.to_string_lossy().to_string()),
+            reason,
+            warnings: Vec::new(),
+        };
+
+        let path = match clients::skill_root(target.client, target.skill_type, project_path) {
+            Ok(root) => root.join(name),
+            Err(e) => {
+                results.push(result(InstallStatus::Failed, None, Some(e)));
+                continue;
+            }
+        };
+
+        if let Some((_, other)) = written.iter().find(|(p, _)| p == &path) {
+            results.push(result(
+                InstallStatus::Skipped,
+                Some(&path),
+                Some(format!("Shares its skill directory with {}", other.as_str())),
+            ));
+            continue;
+        }
+
+        let up_to_date = match shared {
+            Some(source) => shared_store::is_link_to(&path, source),
+            None => matches_installed(&path, files),
+        };
+        if up_to_date {
+            written.push((path.clone(), target.client));
+            results.push(result(InstallStatus::Skipped, Some(&path), Some("Already up to date".to_string())));
+            continue;
+        }
+
+        if let Some(Err(e)) = backups.map(|area| area.snapshot(&path, BackupReason::Install)) {
+            results.push(result(InstallStatus::Failed, Some(&path), Some(e)));
+            continue;
+        }
+
+        let installed = match shared {
+            Some(source) => shared_store::link_skill(&path, source),
+            None => install_files(&path, files),
+        };
+        match installed {
+            Ok(()) => {
+                written.push((path.clone(), target.client));
+                results.push(result(InstallStatus::Installed, Some(&path), None));
+            }
+            Err(e) => results.push(result(InstallStatus::Failed, Some(&path), Some(e))),
+        }
+    }
+
+    results
+}
+
+pub fn install_prepared(
+    app: &AppHandle,
+    files: &[SkillFile],
+    name: &str,
+    targets: &[InstallTarget],
+    project_path: Option<&str>,
+    mode: InstallMode,
+    source: Option<&LockSource>,
+) -> Result<Vec<ClientInstallResult>, String> {
+    let backups = BackupArea::open(app)?;
+    let mut results = match mode {
+        InstallMode::Copy => install_to_targets(files, name, targets, project_path, None, Some(&backups)),
+        InstallMode::Link => {
+            shared_store::check_owner(app, name, source, files)?;
+            let shared = shared_store::shared_root(app)?.join(name);
+            if !matches_installed(&shared, files) {
+                backups.snapshot(&shared, BackupReason::Install)?;
+            }
+            let shared = shared_store::install_shared(app, name, files)?;
+            install_to_targets(files, name, targets, project_path, Some(&shared), Some(&backups))
+        }
+    };
+
+    // The files are in place by now, so failing to record them is a warning, not a failed install
+    let mut warnings = Vec::new();
+    if mode == InstallMode::Link {
+        if let Err(e) = shared_store::record_links(app, name, source, &results) {
+            warnings.push(format!("Failed to record shared links: {}", e));
+        }
+    }
+    if let Err(e) = baseline::record_baselines(app, &results) {
+        warnings.push(format!("Failed to record the installed version: {}", e));
+    }
+    if let Err(e) = lockfile::record_install(project_path, name, &results, source) {
+        warnings.push(format!("Failed to update {}: {}", lockfile::LOCK_FILE, e));
+    }
+
+    for result in results.iter_mut().filter(|r| r.status != InstallStatus::Failed) {
+        result.warnings.extend(warnings.iter().cloned());
+    }
+    Ok(results)
+}
+
+fn install_with_mode(
+    app: &AppHandle,
+    raw_file_url: &str,
+    name: &str,
+    targets: &[InstallTarget],
+    project_path: Option<&str>,
+    mode: InstallMode,
+) -> Result<Vec<ClientInstallResult>, String> {
+    clients::validate_skill_name(name)?;
+    let files = fetch_skill_files(raw_file_url)?;
+    validate_skill_files(&files)?;
+    let source = LockSource::Url { url: raw_file_url.to_string() };
+    install_prepared(app, &files, name, targets, project_path, mode, Some(&source))
+}
+
+// Collapses the result of a single-target install into the installed path or an error
+pub fn single_result(mut results: Vec<ClientInstallResult>) -> Result<String, String> {
+    let result = results.pop()
+        .ok_or_else(|| "Install produced no result".to_string())?;
+
+    match result.status {
+        InstallStatus::Failed => Err(result.reason.unwrap_or_else(|| "Install failed".to_string())),
+        _ => result.path.ok_or_else(|| "Invalid file path".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn install_skill(
+    app: AppHandle,
+    raw_file_url: String,
+    name: String,
+    client: Client,
+    skill_type: SkillType,
+    project_path: Option<String>,
+    mode: Option<InstallMode>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let targets = [InstallTarget { client, skill_type }];
+        let mode = mode.unwrap_or_default();
+        single_result(install_with_mode(&app, &raw_file_url, &name, &targets, project_path.as_deref(), mode)?)
+    })
+    .await
+    .map_err(|e| format!("Install task failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn install_skill_to_clients(
+    app: AppHandle,
+    raw_file_url: String,
+    name: String,
+    targets: Vec<InstallTarget>,
+    project_path: Option<String>,
+    mode: Option<InstallMode>,
+) -> Result<Vec<ClientInstallResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mode = mode.unwrap_or_default();
+        install_with_mode(&app, &raw_file_url, &name, &targets, project_path.as_deref(), mode)
+    })
+    .await
+    .map_err(|e| format!("Install task failed: {}", e))?
+}
+
+// Another client's copy of the skill that is still on disk once `target` is gone
+fn remaining_copy(name: &str, client: Client, skill_type: SkillType, project_path: Option<&str>, target: &Path) -> Option<(Client, PathBuf)> {
+    ALL_CLIENTS.iter()
+        .filter(|&&other| other != client)
+        .find_map(|&other| {
+            let path = clients::skill_root(other, skill_type, project_path).ok()?.join(name);
+            (path != target && fs::symlink_metadata(&path).is_ok()).then_some((other, path))
+        })
+}
+
+// The installed record stays while other clients still have the skill; if it named this
+// client it is moved to one of them, otherwise it is only removed with the last copy
+fn forget_record(
+    app: &AppHandle,
+    id: &str,
+    name: &str,
+    client: Client,
+    skill_type: SkillType,
+    project_path: Option<&str>,
+    target: &Path,
+) -> Result<bool, String> {
+    match remaining_copy(name, client, skill_type, project_path, target) {
+        Some((other, path)) => {
+            installed::retarget_installed(app, id, "skill", client, other, &clients::path_to_string(&path)?)?;
+            Ok(false)
+        }
+        None => installed::remove_installed(app, id, "skill"),
+    }
+}
+
+fn uninstall(
+    app: &AppHandle,
+    id: &str,
+    name: &str,
+    client: Client,
+    skill_type: SkillType,
+    project_path: Option<&str>,
+) -> Result<UninstallResult, String> {
+    clients::validate_skill_name(name)?;
+    let root = clients::skill_root(client, skill_type, project_path)?;
+    let target = root.join(name);
+
+    if fs::symlink_metadata(&target).is_err() {
+        let removed_record = forget_record(app, id, name, client, skill_type, project_path, &target)?;
+        return Ok(UninstallResult {
+            path: clients::path_to_string(&target)?,
+            removed_files: false,
+            removed_record,
+        });
+    }
+
+    ensure_within_root(&root, &target)?;
+
+    BackupArea::open(app)?.snapshot(&target, BackupReason::Uninstall)?;
+
+    // Move the directory aside first so the store and the filesystem change together:
+    // if the store can't be updated, the skill is put back where it was.
+    let trash = sibling_temp_path(&target, "removing");
+    fs::rename(&target, &trash)
+        .map_err(|e| format!("Failed to remove skill: {}", e))?;
+
+    let removed_record = match forget_record(app, id, name, client, skill_type, project_path, &target) {
+        Ok(removed) => removed,
+        Err(e) => {
+            let _ = fs::rename(&trash, &target);
+            return Err(e);
+        }
+    };
+
+    let _ = remove_path(&trash);
+    let _ = shared_store::forget_link(app, &target);
+    let _ = baseline::forget_baseline(app, &target);
+    if skill_type == SkillType::Project {
+        let _ = lockfile::forget_install(project_path, &target);
+    }
+
+    Ok(UninstallResult {
+        path: clients::path_to_string(&target)?,
+        removed_files: true,
+        removed_record,
+    })
+}
+
+#[tauri::command]
+pub async fn uninstall_skill(
+    app: AppHandle,
+    id: String,
+    name: String,
+    client: Client,
+    skill_type: SkillType,
+    project_path: Option<String>,
+) -> Result<UninstallResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        uninstall(&app, &id, &name, client, skill_type, project_path.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Uninstall task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_relative_path_accepts_paths_inside_the_skill() {
+        assert_eq!(safe_relative_path("SKILL.md"), Some(PathBuf::from("SKILL.md")));
+        assert_eq!(safe_relative_path("./scripts/run.sh"), Some(Path::new("scripts").join("run.sh")));
+    }
+
+    #[test]
+    fn safe_relative_path_rejects_escapes() {
+        assert_eq!(safe_relative_path("../SKILL.md"), None);
+        assert_eq!(safe_relative_path("scripts/../../x"), None);
+        assert_eq!(safe_relative_path("/etc/passwd"), None);
+        assert_eq!(safe_relative_path(""), None);
+        assert_eq!(safe_relative_path("."), None);
+    }
+
+    #[test]
+    fn matches_installed_ignores_hidden_files() {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-matches-installed", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let file = |path: &str, contents: &str| SkillFile { path: PathBuf::from(path), contents: contents.into(), mode: None };
+        let files = vec![file(SKILL_FILE, "skill"), file(".env.example", "A=1"), file(".github/ci.yml", "on: push")];
+        write_skill_files(&dir, &files).unwrap();
+
+        let same = matches_installed(&dir, &files);
+        let changed = matches_installed(&dir, &[file(SKILL_FILE, "edited"), file(".env.example", "A=1")]);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(same);
+        assert!(!changed);
+    }
+
+    #[test]
+    fn safe_relative_path_treats_backslashes_as_separators() {
+        assert_eq!(safe_relative_path("scripts\\run.sh"), Some(Path::new("scripts").join("run.sh")));
+        assert_eq!(safe_relative_path("..\\evil.sh"), None);
+        assert_eq!(safe_relative_path("\\evil.sh"), None);
+        assert_eq!(safe_relative_path("C:\\evil.sh"), None);
+        assert_eq!(safe_relative_path("C:/evil.sh"), None);
+        assert_eq!(safe_relative_path("notes.txt:stream"), None);
+    }
+
+    #[test]
+    fn remaining_copy_finds_other_clients_copies_only() {
+        let project = std::env::temp_dir().join(format!("skiller-test-{}-remaining", std::process::id()));
+        let _ = fs::remove_dir_all(&project);
+        let project_path = project.to_str().unwrap();
+        let target = project.join(".claude/skills/pdf");
+        fs::create_dir_all(&target).unwrap();
+
+        let alone = remaining_copy("pdf", Client::ClaudeCode, SkillType::Project, Some(project_path), &target);
+        fs::create_dir_all(project.join(".cursor/skills/pdf")).unwrap();
+        let shared = remaining_copy("pdf", Client::ClaudeCode, SkillType::Project, Some(project_path), &target);
+        let other = remaining_copy("docx", Client::ClaudeCode, SkillType::Project, Some(project_path), &project.join(".claude/skills/docx"));
+        let _ = fs::remove_dir_all(&project);
+
+        assert_eq!(alone, None);
+        assert_eq!(shared, Some((Client::Cursor, project.join(".cursor/skills/pdf"))));
+        assert_eq!(other, None);
+    }
+}
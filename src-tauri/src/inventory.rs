@@ -0,0 +1,329 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::clients::{self, Client, SkillType, ALL_CLIENTS};
+use crate::installed::{self, InstalledItem};
+use crate::remote::SKILL_FILE;
+
+#[derive(Debug, Clone)]
+pub struct SkillRoot {
+    pub path: PathBuf,
+    pub clients: Vec<Client>,
+    pub skill_type: SkillType,
+    pub project_path: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscoveredSkill {
+    pub name: String,
+    pub path: PathBuf,
+    pub root: SkillRoot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkillStatus {
+    Tracked,
+    Untracked,
+    MissingOnDisk,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryEntry {
+    pub name: String,
+    pub path: String,
+    pub clients: Vec<Client>,
+    pub skill_type: SkillType,
+    pub project_path: Option<String>,
+    pub status: SkillStatus,
+    pub installed_id: Option<String>,
+}
+
+fn push_root(roots: &mut Vec<SkillRoot>, path: PathBuf, client: Client, skill_type: SkillType, project_path: Option<&str>) {
+    if let Some(existing) = roots.iter_mut().find(|r| r.path == path) {
+        if !existing.clients.contains(&client) {
+            existing.clients.push(client);
+        }
+        return;
+    }
+    roots.push(SkillRoot {
+        path,
+        clients: vec![client],
+        skill_type,
+        project_path: project_path.map(|p| p.to_string()),
+    });
+}
+
+// Every personal root plus the project roots of each given project. Clients that
+// share a directory (Claude and Claude Code both use ~/.claude/skills) share one root.
+pub fn skill_roots(project_paths: &[String]) -> Vec<SkillRoot> {
+    let mut roots = Vec::new();
+
+    for client in ALL_CLIENTS {
+        if let Ok(path) = clients::skill_root(client, SkillType::Personal, None) {
+            push_root(&mut roots, path, client, SkillType::Personal, None);
+        }
+    }
+
+    for project in project_paths.iter().filter(|p| !p.is_empty()) {
+        for client in ALL_CLIENTS {
+            if client.project_skill_dir().is_none() {
+                continue;
+            }
+            if let Ok(path) = clients::skill_root(client, SkillType::Project, Some(project)) {
+                push_root(&mut roots, path, client, SkillType::Project, Some(project));
+            }
+        }
+    }
+
+    roots
+}
+
+pub fn is_skill_dir(path: &Path) -> bool {
+    path.join(SKILL_FILE).is_file()
+}
+
+pub fn discover_in_root(root: &SkillRoot) -> Vec<DiscoveredSkill> {
+    let Ok(entries) = fs::read_dir(&root.path) else {
+        return Vec::new();
+    };
+
+    let mut skills: Vec<DiscoveredSkill> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Hidden entries include the staging and removal directories the installer leaves behind
+            if name.starts_with('.') {
+                return None;
+            }
+            let path = entry.path();
+            if !is_skill_dir(&path) {
+                return None;
+            }
+            Some(DiscoveredSkill { name, path, root: root.clone() })
+        })
+        .collect();
+
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    skills
+}
+
+pub fn discover_skills(project_paths: &[String]) -> Vec<DiscoveredSkill> {
+    skill_roots(project_paths)
+        .iter()
+        .flat_map(discover_in_root)
+        .collect()
+}
+
+// The open project a project-scoped record belongs to: the one holding its recorded path,
+// or for records without a path the only open project. None when it can't be told apart.
+fn record_project<'a>(item: &InstalledItem, project_paths: &'a [String]) -> Option<&'a str> {
+    if item.skill_type != Some(SkillType::Project) || item.client?.project_skill_dir().is_none() {
+        return None;
+    }
+    let mut projects = project_paths.iter().filter(|p| !p.is_empty());
+
+    match item.path.as_deref().filter(|p| !p.is_empty()) {
+        Some(path) => {
+            let path = clients::expand_home(path).ok()?;
+            projects
+                .find(|p| clients::expand_home(p).map(|root| path.starts_with(root)).unwrap_or(false))
+                .map(|p| p.as_str())
+        }
+        None => match (projects.next(), projects.next()) {
+            (Some(only), None) => Some(only.as_str()),
+            _ => None,
+        },
+    }
+}
+
+fn record_matches(item: &InstalledItem, skill: &DiscoveredSkill, project_paths: &[String]) -> bool {
+    if let Some(path) = item.path.as_deref().filter(|p| !p.is_empty()) {
+        return clients::expand_home(path).map(|p| p == skill.path).unwrap_or(false);
+    }
+
+    let Some(client) = item.client else {
+        return false;
+    };
+    let skill_type = item.skill_type.unwrap_or(SkillType::Personal);
+    let scope_matches = skill.root.skill_type == skill_type
+        || (skill_type == SkillType::Project && client.project_skill_dir().is_none());
+    // With several projects open a pathless record could belong to any of them; it still
+    // claims only one skill (see reconcile), but when its project is known it must be that one
+    let project_matches = skill.root.skill_type != SkillType::Project
+        || record_project(item, project_paths).is_none_or(|p| skill.root.project_path.as_deref() == Some(p));
+
+    item.name.eq_ignore_ascii_case(&skill.name)
+        && skill.root.clients.contains(&client)
+        && scope_matches
+        && project_matches
+}
+
+fn expected_path(item: &InstalledItem, project: Option<&str>) -> Option<PathBuf> {
+    if let Some(path) = item.path.as_deref().filter(|p| !p.is_empty()) {
+        return clients::expand_home(path).ok();
+    }
+    let client = item.client?;
+    let skill_type = item.skill_type.unwrap_or(SkillType::Personal);
+    clients::skill_root(client, skill_type, project)
+        .ok()
+        .map(|root| root.join(&item.name))
+}
+
+pub fn reconcile(
+    discovered: &[DiscoveredSkill],
+    records: &[InstalledItem],
+    project_paths: &[String],
+) -> Result<Vec<InventoryEntry>, String> {
+    let skill_records: Vec<&InstalledItem> = records.iter().filter(|i| i.kind == "skill").collect();
+    let mut matched = vec![false; skill_records.len()];
+    let mut entries = Vec::new();
+
+    for skill in discovered {
+        // A record describes one copy on disk, so it is claimed by the first skill it matches
+        let record_index = skill_records.iter().enumerate()
+            .position(|(index, item)| !matched[index] && record_matches(item, skill, project_paths));
+        if let Some(index) = record_index {
+            matched[index] = true;
+        }
+
+        entries.push(InventoryEntry {
+            name: skill.name.clone(),
+            path: clients::path_to_string(&skill.path)?,
+            clients: skill.root.clients.clone(),
+            skill_type: skill.root.skill_type,
+            project_path: skill.root.project_path.clone(),
+            status: if record_index.is_some() { SkillStatus::Tracked } else { SkillStatus::Untracked },
+            installed_id: record_index.map(|i| skill_records[i].id.clone()),
+        });
+    }
+
+    for (item, _) in skill_records.iter().zip(matched).filter(|(_, m)| !m) {
+        let project = record_project(item, project_paths);
+        let path = expected_path(item, project)
+            .map(|p| clients::path_to_string(&p))
+            .transpose()?
+            .unwrap_or_default();
+
+        entries.push(InventoryEntry {
+            name: item.name.clone(),
+            path,
+            clients: item.client.into_iter().collect(),
+            skill_type: item.skill_type.unwrap_or(SkillType::Personal),
+            project_path: project.map(|p| p.to_string()),
+            status: SkillStatus::MissingOnDisk,
+            installed_id: Some(item.id.clone()),
+        });
+    }
+
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn scan_installed_skills(
+    app: AppHandle,
+    project_paths: Option<Vec<String>>,
+) -> Result<Vec<InventoryEntry>, String> {
+    let project_paths = project_paths.unwrap_or_default();
+    let records = installed::load_installed(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let discovered = discover_skills(&project_paths);
+        reconcile(&discovered, &records, &project_paths)
+    })
+    .await
+    .map_err(|e| format!("Skill scan failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projects() -> Vec<String> {
+        vec!["/work/a".to_string(), "/work/b".to_string()]
+    }
+
+    fn project_skill(project: &str, name: &str) -> DiscoveredSkill {
+        let root = Path::new(project).join(".claude/skills");
+        DiscoveredSkill {
+            name: name.to_string(),
+            path: root.join(name),
+            root: SkillRoot {
+                path: root,
+                clients: vec![Client::ClaudeCode],
+                skill_type: SkillType::Project,
+                project_path: Some(project.to_string()),
+            },
+        }
+    }
+
+    fn record(id: &str, name: &str, path: Option<&str>) -> InstalledItem {
+        InstalledItem {
+            id: id.to_string(),
+            kind: "skill".to_string(),
+            name: name.to_string(),
+            installed_at: String::new(),
+            client: Some(Client::ClaudeCode),
+            skill_type: Some(SkillType::Project),
+            path: path.map(|p| p.to_string()),
+        }
+    }
+
+    fn statuses(entries: &[InventoryEntry]) -> Vec<(&str, SkillStatus, Option<&str>)> {
+        entries.iter().map(|e| (e.path.as_str(), e.status, e.installed_id.as_deref())).collect()
+    }
+
+    #[test]
+    fn a_pathless_record_claims_one_copy_when_several_projects_are_open() {
+        let discovered = [project_skill("/work/a", "pdf"), project_skill("/work/b", "pdf")];
+        let entries = reconcile(&discovered, &[record("pdf", "pdf", None)], &projects()).unwrap();
+
+        assert_eq!(statuses(&entries), [
+            ("/work/a/.claude/skills/pdf", SkillStatus::Tracked, Some("pdf")),
+            ("/work/b/.claude/skills/pdf", SkillStatus::Untracked, None),
+        ]);
+    }
+
+    #[test]
+    fn a_pathless_record_only_matches_the_single_open_project() {
+        let only = vec!["/work/b".to_string()];
+        let stray = project_skill("/work/a", "pdf");
+        assert!(!record_matches(&record("pdf", "pdf", None), &stray, &only));
+        assert!(record_matches(&record("pdf", "pdf", None), &project_skill("/work/b", "pdf"), &only));
+    }
+
+    #[test]
+    fn missing_records_point_at_their_own_project() {
+        let records = [
+            record("pdf", "pdf", Some("/work/b/.claude/skills/pdf")),
+            record("docx", "docx", None),
+        ];
+        let entries = reconcile(&[], &records, &projects()).unwrap();
+
+        assert_eq!(entries[0].path, "/work/b/.claude/skills/pdf");
+        assert_eq!(entries[0].project_path.as_deref(), Some("/work/b"));
+        assert_eq!(entries[0].status, SkillStatus::MissingOnDisk);
+        // Two projects are open and the record doesn't say which one it was installed into
+        assert_eq!(entries[1].path, "");
+        assert_eq!(entries[1].project_path, None);
+
+        let entries = reconcile(&[], &records[1..], &projects()[1..]).unwrap();
+        assert_eq!(entries[0].path, "/work/b/.claude/skills/docx");
+        assert_eq!(entries[0].project_path.as_deref(), Some("/work/b"));
+    }
+
+    #[test]
+    fn recorded_paths_match_exactly() {
+        let records = [record("pdf", "pdf", Some("/work/b/.claude/skills/pdf"))];
+        let discovered = [project_skill("/work/a", "pdf"), project_skill("/work/b", "pdf")];
+        let entries = reconcile(&discovered, &records, &projects()).unwrap();
+
+        assert_eq!(statuses(&entries), [
+            ("/work/a/.claude/skills/pdf", SkillStatus::Untracked, None),
+            ("/work/b/.claude/skills/pdf", SkillStatus::Tracked, Some("pdf")),
+        ]);
+    }
+}
@@ -1,4 +1,31 @@
+mod archive;
+mod backups;
+mod baseline;
+mod bundle;
+mod claude_config;
+mod clients;
 mod commands;
+mod conflicts;
+mod diff;
+mod hashing;
+mod installed;
+mod installer;
+mod inventory;
+mod lint;
+mod lockfile;
+mod marketplace;
+mod mcp;
+mod plugin_conflicts;
+mod plugin_inspect;
+mod plugin_settings;
+mod plugins;
+mod project;
+mod remote;
+mod scaffold;
+mod shared_store;
+mod skill_md;
+mod updates;
+mod watcher;
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
@@ -269,6 +296,10 @@ pub fn run() {
                 }
             });
 
+            // Personal skill roots are watched from launch; the frontend adds projects with watch_skill_roots
+            let watch_handle = app.handle().clone();
+            std::thread::spawn(move || watcher::start_at_launch(watch_handle));
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -277,6 +308,48 @@ pub fn run() {
             commands::get_installed_terminals,
             commands::get_default_download_path,
             commands::download_skill,
+            installer::install_skill,
+            installer::install_skill_to_clients,
+            archive::install_skill_from_path,
+            bundle::export_skill_bundle,
+            bundle::import_skill_bundle,
+            backups::list_skill_backups,
+            backups::restore_skill_backup,
+            backups::delete_skill_backup,
+            backups::get_backup_settings,
+            backups::set_backup_settings,
+            conflicts::find_skill_conflicts,
+            diff::diff_skill_update,
+            installer::uninstall_skill,
+            inventory::scan_installed_skills,
+            scaffold::create_skill,
+            scaffold::list_skill_templates,
+            lint::lint_skill,
+            lockfile::sync_skills_lock,
+            lockfile::verify_skills_lock,
+            marketplace::add_marketplace,
+            marketplace::list_marketplace_plugins,
+            marketplace::list_marketplaces,
+            marketplace::refresh_marketplaces,
+            marketplace::remove_marketplace,
+            mcp::add_mcp_server,
+            mcp::list_mcp_servers,
+            mcp::remove_mcp_server,
+            mcp::set_mcp_server_enabled,
+            mcp::validate_mcp_server,
+            plugin_conflicts::check_plugin_conflicts,
+            plugin_conflicts::find_plugin_conflicts,
+            plugin_inspect::inspect_plugin,
+            plugin_settings::list_plugin_settings,
+            plugin_settings::set_plugin_enabled,
+            plugins::install_plugin,
+            plugins::uninstall_plugin,
+            project::inspect_project,
+            shared_store::repair_skill_links,
+            skill_md::parse_skill,
+            updates::check_skill_updates,
+            watcher::watch_skill_roots,
+            watcher::unwatch_skill_roots,
             set_prevent_hide,
             register_shortcut,
             unregister_shortcut,
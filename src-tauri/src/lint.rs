@@ -0,0 +1,328 @@
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+
+use crate::clients;
+use crate::hashing;
+use crate::remote::SKILL_FILE;
+use crate::skill_md::{self, ValidationCode};
+
+// Limits on what gets loaded into an agent's context. SKILL.md bodies over ~500 lines
+// should be split into reference files.
+pub const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+pub const MAX_SKILL_BYTES: u64 = 30 * 1024 * 1024;
+pub const MAX_BODY_LINES: usize = 500;
+
+// Folders whose files SKILL.md is expected to point at by relative path
+const RESOURCE_DIRS: [&str; 3] = ["scripts/", "references/", "assets/"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintCode {
+    MissingSkillFile,
+    MissingReference,
+    AbsoluteHomePath,
+    FileTooLarge,
+    SkillTooLarge,
+    BodyTooLong,
+    NotExecutable,
+    UnreadableFile,
+    // Serialized as the skill_md code, e.g. "missing-description"
+    #[serde(untagged)]
+    Frontmatter(ValidationCode),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: LintCode,
+    // Relative to the skill directory
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub path: String,
+    pub diagnostics: Vec<Diagnostic>,
+    pub errors: usize,
+    pub warnings: usize,
+    // No errors; warnings don't fail the lint
+    pub passed: bool,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+// Compiler-style "file:line: severity: message", for printing on a terminal
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}: ", file, line)?,
+            (Some(file), None) => write!(f, "{}: ", file)?,
+            _ => {}
+        }
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, code: LintCode, file: Option<&str>, line: Option<usize>, message: String) -> Self {
+        Diagnostic {
+            severity,
+            code,
+            file: file.map(|f| f.to_string()),
+            line,
+            message,
+        }
+    }
+}
+
+fn lint_frontmatter(content: &str, dir_name: Option<&str>, diagnostics: &mut Vec<Diagnostic>) {
+    let (skill, errors) = skill_md::check_skill(content, dir_name);
+
+    for error in errors {
+        let severity = if error.code.blocks_install() { Severity::Error } else { Severity::Warning };
+        diagnostics.push(Diagnostic::new(severity, LintCode::Frontmatter(error.code), Some(SKILL_FILE), error.line, error.message));
+    }
+
+    if let Some(skill) = skill {
+        let lines = skill.body.lines().count();
+        if lines > MAX_BODY_LINES {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                LintCode::BodyTooLong,
+                Some(SKILL_FILE),
+                Some(skill.body_line),
+                format!("Body is {} lines; consider moving detail into references/ (limit {})", lines, MAX_BODY_LINES),
+            ));
+        }
+    }
+}
+
+fn trim_reference(candidate: &str) -> &str {
+    let end = candidate
+        .find(|c: char| c.is_whitespace() || "`'\"()<>[]{},;|*".contains(c))
+        .unwrap_or(candidate.len());
+    let candidate = &candidate[..end];
+    // Drop anchors and sentence punctuation: "see scripts/run.sh." or "references/api.md#auth"
+    let candidate = candidate.split('#').next().unwrap_or(candidate);
+    candidate.trim_end_matches(['.', ':', '!', '?'])
+}
+
+// Relative paths under scripts/, references/ or assets/ mentioned on a line, either in a
+// markdown link or as plain text. Paths that are part of a longer path or URL are ignored.
+fn find_references(line: &str) -> Vec<String> {
+    let mut references = Vec::new();
+
+    for dir in RESOURCE_DIRS {
+        let mut from = 0;
+        while let Some(offset) = line[from..].find(dir) {
+            let start = from + offset;
+            from = start + dir.len();
+
+            let preceded_by_path = line[..start]
+                .chars()
+                .next_back()
+                .map(|c| c.is_alphanumeric() || matches!(c, '/' | '.' | '-' | '_' | '~' | '\\'))
+                .unwrap_or(false);
+            if preceded_by_path {
+                continue;
+            }
+
+            let reference = trim_reference(&line[start..]);
+            if reference.len() > dir.len() && !references.iter().any(|r| r == reference) {
+                references.push(reference.to_string());
+            }
+        }
+    }
+
+    references
+}
+
+fn lint_references(dir: &Path, content: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (index, line) in content.lines().enumerate() {
+        for reference in find_references(line) {
+            // Globs and placeholders ("scripts/<name>.py") can't be checked
+            if reference.contains(['<', '*', '{', '$']) {
+                continue;
+            }
+            if !dir.join(reference.trim_end_matches('/')).exists() {
+                diagnostics.push(Diagnostic::new(
+                    Severity::Error,
+                    LintCode::MissingReference,
+                    Some(SKILL_FILE),
+                    Some(index + 1),
+                    format!("References {} which does not exist", reference),
+                ));
+            }
+        }
+    }
+}
+
+// Paths into a particular user's home directory only work on the author's machine
+fn home_path_on_line(line: &str, home: Option<&str>) -> Option<String> {
+    if let Some(home) = home.filter(|h| h.len() > 1 && line.contains(*h)) {
+        return Some(home.to_string());
+    }
+
+    for prefix in ["/Users/", "/home/", "C:\\Users\\", "C:/Users/"] {
+        let Some(start) = line.find(prefix) else { continue };
+        let user: String = line[start + prefix.len()..]
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            .collect();
+        // "/home/user/" and similar placeholders are fine in examples
+        if !user.is_empty() && !matches!(user.as_str(), "user" | "username" | "me" | "you" | "name") {
+            return Some(format!("{}{}", prefix, user));
+        }
+    }
+    None
+}
+
+fn lint_text_file(relative: &str, content: &str, home: Option<&str>, diagnostics: &mut Vec<Diagnostic>) {
+    for (index, line) in content.lines().enumerate() {
+        if let Some(path) = home_path_on_line(line, home) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                LintCode::AbsoluteHomePath,
+                Some(relative),
+                Some(index + 1),
+                format!("Absolute path into a home directory ({}); use a path relative to the skill", path),
+            ));
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+// Windows has no executable bit; scripts are run through their interpreter
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+fn lint_files(dir: &Path, diagnostics: &mut Vec<Diagnostic>) -> Result<(), String> {
+    let home = clients::home_dir().ok().map(|h| h.to_string_lossy().to_string());
+    let mut total: u64 = 0;
+
+    for relative in hashing::list_files(dir)? {
+        let path = dir.join(&relative);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                diagnostics.push(Diagnostic::new(
+                    Severity::Error,
+                    LintCode::UnreadableFile,
+                    Some(&relative),
+                    None,
+                    format!("Failed to read file: {}", e),
+                ));
+                continue;
+            }
+        };
+
+        let size = bytes.len() as u64;
+        total += size;
+        if size > MAX_FILE_BYTES {
+            diagnostics.push(Diagnostic::new(
+                Severity::Error,
+                LintCode::FileTooLarge,
+                Some(&relative),
+                None,
+                format!("File is {} KB, the limit is {} KB", size / 1024, MAX_FILE_BYTES / 1024),
+            ));
+        }
+
+        // Binary files are only checked for size
+        let Ok(content) = std::str::from_utf8(&bytes) else { continue };
+        lint_text_file(&relative, content, home.as_deref(), diagnostics);
+
+        if relative.starts_with("scripts/") && content.starts_with("#!") && !is_executable(&path) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                LintCode::NotExecutable,
+                Some(&relative),
+                Some(1),
+                "Script has a shebang but is not executable (chmod +x)".to_string(),
+            ));
+        }
+    }
+
+    if total > MAX_SKILL_BYTES {
+        diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            LintCode::SkillTooLarge,
+            None,
+            None,
+            format!("Skill is {} MB, the limit is {} MB", total / 1024 / 1024, MAX_SKILL_BYTES / 1024 / 1024),
+        ));
+    }
+    Ok(())
+}
+
+pub fn lint(path: &Path) -> Result<LintReport, String> {
+    // Accepts either a skill directory or the SKILL.md inside it
+    let file = skill_md::resolve_skill_file(path);
+    let dir = file.parent()
+        .ok_or_else(|| format!("{} is not a skill directory", path.display()))?;
+    if !dir.is_dir() {
+        return Err(format!("{} is not a skill directory", dir.display()));
+    }
+
+    let mut diagnostics = Vec::new();
+    match fs::read_to_string(&file) {
+        Ok(content) => {
+            let dir_name = dir.file_name().map(|n| n.to_string_lossy().to_string());
+            lint_frontmatter(&content, dir_name.as_deref(), &mut diagnostics);
+            lint_references(dir, &content, &mut diagnostics);
+        }
+        Err(_) => diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            LintCode::MissingSkillFile,
+            Some(SKILL_FILE),
+            None,
+            format!("No readable {} in the skill directory", SKILL_FILE),
+        )),
+    }
+    lint_files(dir, &mut diagnostics)?;
+
+    diagnostics.sort_by(|a, b| (a.severity, &a.file, a.line).cmp(&(b.severity, &b.file, b.line)));
+    let errors = diagnostics.iter().filter(|d| d.severity == Severity::Error).count();
+    let warnings = diagnostics.iter().filter(|d| d.severity == Severity::Warning).count();
+
+    Ok(LintReport {
+        path: clients::path_to_string(dir)?,
+        diagnostics,
+        errors,
+        warnings,
+        passed: errors == 0,
+    })
+}
+
+#[tauri::command]
+pub async fn lint_skill(path: String) -> Result<LintReport, String> {
+    tauri::async_runtime::spawn_blocking(move || lint(&clients::expand_home(&path)?))
+        .await
+        .map_err(|e| format!("Lint failed: {}", e))?
+}
@@ -0,0 +1,390 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::archive;
+use crate::backups::{BackupArea, BackupReason};
+use crate::clients::{self, Client, SkillType, ALL_CLIENTS};
+use crate::hashing;
+use crate::installer::{self, ClientInstallResult, InstallStatus, SkillFile};
+use crate::inventory;
+
+pub const LOCK_FILE: &str = "skills.lock";
+const LOCK_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum LockSource {
+    Url { url: String },
+    Path { path: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockEntry {
+    pub name: String,
+    pub clients: Vec<Client>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<LockSource>,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    // Keyed by the skill directory relative to the project root, e.g. ".claude/skills/pdf"
+    pub skills: BTreeMap<String, LockEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockStatus {
+    Ok,
+    Missing,
+    Drifted,
+    Unlocked,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockEntryReport {
+    pub path: String,
+    pub name: String,
+    pub status: LockStatus,
+    pub expected_hash: Option<String>,
+    pub actual_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockVerifyReport {
+    pub lockfile: String,
+    pub entries: Vec<LockEntryReport>,
+    // 0 when every locked skill matches, 1 otherwise, so a CLI can exit with it directly
+    pub exit_code: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncAction {
+    UpToDate,
+    Installed,
+    Repaired,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockSyncResult {
+    pub path: String,
+    pub name: String,
+    pub action: SyncAction,
+    // False when the source has changed since it was locked, so the installed copy differs
+    pub hash_matches: bool,
+    pub message: Option<String>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Lockfile {
+            version: LOCK_VERSION,
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+pub fn lock_path(project: &Path) -> PathBuf {
+    project.join(LOCK_FILE)
+}
+
+pub fn read_lockfile(project: &Path) -> Result<Lockfile, String> {
+    let path = lock_path(project);
+    if !path.exists() {
+        return Ok(Lockfile::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let lockfile: Lockfile = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid {}: {}", LOCK_FILE, e))?;
+
+    if lockfile.version > LOCK_VERSION {
+        return Err(format!("{} version {} is newer than this version of Skiller supports", LOCK_FILE, lockfile.version));
+    }
+    Ok(lockfile)
+}
+
+pub fn write_lockfile(project: &Path, lockfile: &Lockfile) -> Result<(), String> {
+    let path = lock_path(project);
+    let mut content = serde_json::to_string_pretty(lockfile)
+        .map_err(|e| format!("Failed to serialize {}: {}", LOCK_FILE, e))?;
+    content.push('\n');
+
+    let partial = installer::sibling_temp_path(&path, "partial");
+    fs::write(&partial, content)
+        .map_err(|e| format!("Failed to write {}: {}", LOCK_FILE, e))?;
+    fs::rename(&partial, &path)
+        .map_err(|e| format!("Failed to write {}: {}", LOCK_FILE, e))
+}
+
+fn relative_key(project: &Path, skill_dir: &Path) -> Option<String> {
+    skill_dir.strip_prefix(project)
+        .ok()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+}
+
+// Records project-scope results of an install in the project's lockfile
+pub fn record_install(
+    project_path: Option<&str>,
+    name: &str,
+    results: &[ClientInstallResult],
+    source: Option<&LockSource>,
+) -> Result<(), String> {
+    let Some(project_path) = project_path.filter(|p| !p.is_empty()) else {
+        return Ok(());
+    };
+    let project = clients::expand_home(project_path)?;
+
+    let project_results: Vec<&ClientInstallResult> = results.iter()
+        .filter(|r| r.skill_type == SkillType::Project && r.status != InstallStatus::Failed)
+        .filter(|r| r.client.project_skill_dir().is_some())
+        .collect();
+    if project_results.is_empty() {
+        return Ok(());
+    }
+
+    let mut lockfile = read_lockfile(&project)?;
+    for result in project_results {
+        let Some(path) = &result.path else { continue };
+        let skill_dir = PathBuf::from(path);
+        let Some(key) = relative_key(&project, &skill_dir) else { continue };
+
+        let hash = hashing::dir_hash(&skill_dir)?;
+        let entry = lockfile.skills.entry(key).or_insert_with(|| LockEntry {
+            name: name.to_string(),
+            clients: Vec::new(),
+            source: None,
+            hash: String::new(),
+        });
+        if !entry.clients.contains(&result.client) {
+            entry.clients.push(result.client);
+        }
+        entry.name = name.to_string();
+        entry.hash = hash;
+        if let Some(source) = source {
+            entry.source = shareable_source(&project, source);
+        }
+    }
+
+    write_lockfile(&project, &lockfile)
+}
+
+pub fn forget_install(project_path: Option<&str>, skill_dir: &Path) -> Result<(), String> {
+    let Some(project_path) = project_path.filter(|p| !p.is_empty()) else {
+        return Ok(());
+    };
+    let project = clients::expand_home(project_path)?;
+    if !lock_path(&project).exists() {
+        return Ok(());
+    }
+
+    let Some(key) = relative_key(&project, skill_dir) else {
+        return Ok(());
+    };
+    let mut lockfile = read_lockfile(&project)?;
+    if lockfile.skills.remove(&key).is_none() {
+        return Ok(());
+    }
+    write_lockfile(&project, &lockfile)
+}
+
+fn entry_report(project: &Path, key: &str, entry: &LockEntry) -> LockEntryReport {
+    let dir = project.join(key);
+    let actual_hash = if inventory::is_skill_dir(&dir) {
+        hashing::dir_hash(&dir).ok()
+    } else {
+        None
+    };
+
+    let status = match &actual_hash {
+        None => LockStatus::Missing,
+        Some(hash) if hash == &entry.hash => LockStatus::Ok,
+        Some(_) => LockStatus::Drifted,
+    };
+
+    LockEntryReport {
+        path: key.to_string(),
+        name: entry.name.clone(),
+        status,
+        expected_hash: Some(entry.hash.clone()),
+        actual_hash,
+    }
+}
+
+pub fn verify(project: &Path) -> Result<LockVerifyReport, String> {
+    let lockfile = read_lockfile(project)?;
+    let mut entries: Vec<LockEntryReport> = lockfile.skills.iter()
+        .map(|(key, entry)| entry_report(project, key, entry))
+        .collect();
+
+    // Project skills that exist on disk but were never locked are reported, not failed
+    let project_path = clients::path_to_string(project)?;
+    let roots = inventory::skill_roots(&[project_path]);
+    for root in roots.iter().filter(|r| r.skill_type == SkillType::Project) {
+        for skill in inventory::discover_in_root(root) {
+            let Some(key) = relative_key(project, &skill.path) else { continue };
+            if lockfile.skills.contains_key(&key) {
+                continue;
+            }
+            entries.push(LockEntryReport {
+                path: key,
+                name: skill.name,
+                status: LockStatus::Unlocked,
+                expected_hash: None,
+                actual_hash: hashing::dir_hash(&skill.path).ok(),
+            });
+        }
+    }
+
+    let failed = entries.iter().any(|e| matches!(e.status, LockStatus::Missing | LockStatus::Drifted));
+    Ok(LockVerifyReport {
+        lockfile: clients::path_to_string(&lock_path(project))?,
+        entries,
+        exit_code: if failed { 1 } else { 0 },
+    })
+}
+
+// A lockfile key names one skill folder directly inside a client's project skill directory;
+// anything else (including the skill directory itself) would let sync replace the wrong folder
+fn skill_dir_for_key(project: &Path, key: &str) -> Result<PathBuf, String> {
+    let relative = installer::safe_relative_path(key)
+        .ok_or_else(|| format!("Invalid lockfile path: {}", key))?;
+    let in_skill_dir = ALL_CLIENTS.iter()
+        .filter_map(|c| c.project_skill_dir())
+        .any(|dir| relative.parent() == Some(Path::new(dir)));
+    let name = relative.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    if !in_skill_dir || clients::validate_skill_name(name).is_err() {
+        return Err(format!("{} is not a skill folder inside a known skill directory", key));
+    }
+    Ok(project.join(relative))
+}
+
+// Path sources are stored relative to the project so the lockfile can be shared; folders
+// outside the project only exist on this machine, so they aren't recorded at all
+fn shareable_source(project: &Path, source: &LockSource) -> Option<LockSource> {
+    match source {
+        LockSource::Url { .. } => Some(source.clone()),
+        LockSource::Path { path } => {
+            let relative = clients::expand_home(path).ok()?.strip_prefix(project).ok()?.to_path_buf();
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            Some(LockSource::Path { path: if relative.is_empty() { ".".to_string() } else { relative } })
+        }
+    }
+}
+
+fn fetch_source(project: &Path, source: &LockSource) -> Result<Vec<SkillFile>, String> {
+    let files = match source {
+        LockSource::Url { url } => installer::fetch_skill_files(url)?,
+        LockSource::Path { path } => {
+            let (_, files) = archive::load_skill_source(&project.join(clients::expand_home(path)?), None)?;
+            files
+        }
+    };
+    installer::validate_skill_files(&files)?;
+    Ok(files)
+}
+
+fn sync_entry(project: &Path, key: &str, entry: &LockEntry, backups: &BackupArea) -> LockSyncResult {
+    let report = entry_report(project, key, entry);
+    let result = |action, hash_matches, message: Option<String>| LockSyncResult {
+        path: key.to_string(),
+        name: entry.name.clone(),
+        action,
+        hash_matches,
+        message,
+    };
+
+    if report.status == LockStatus::Ok {
+        return result(SyncAction::UpToDate, true, None);
+    }
+
+    let Some(source) = &entry.source else {
+        return result(SyncAction::Failed, false, Some("No source recorded in the lockfile".to_string()));
+    };
+    let target = match skill_dir_for_key(project, key) {
+        Ok(target) => target,
+        Err(e) => return result(SyncAction::Failed, false, Some(e)),
+    };
+    let installed = fetch_source(project, source).and_then(|files| {
+        backups.snapshot(&target, BackupReason::LockSync)?;
+        installer::install_files(&target, &files)
+    });
+    if let Err(e) = installed {
+        return result(SyncAction::Failed, false, Some(e));
+    }
+
+    let action = if report.status == LockStatus::Missing { SyncAction::Installed } else { SyncAction::Repaired };
+    let hash_matches = hashing::dir_hash(&target).map(|h| h == entry.hash).unwrap_or(false);
+    let message = if hash_matches {
+        None
+    } else {
+        Some("The source has changed since it was locked".to_string())
+    };
+    result(action, hash_matches, message)
+}
+
+pub fn sync(project: &Path, backups: &BackupArea) -> Result<Vec<LockSyncResult>, String> {
+    let lockfile = read_lockfile(project)?;
+    Ok(lockfile.skills.iter()
+        .map(|(key, entry)| sync_entry(project, key, entry, backups))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn verify_skills_lock(project_path: String) -> Result<LockVerifyReport, String> {
+    tauri::async_runtime::spawn_blocking(move || verify(&clients::expand_home(&project_path)?))
+        .await
+        .map_err(|e| format!("Lockfile verification failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn sync_skills_lock(app: AppHandle, project_path: String) -> Result<Vec<LockSyncResult>, String> {
+    let backups = BackupArea::open(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || sync(&clients::expand_home(&project_path)?, &backups))
+        .await
+        .map_err(|e| format!("Lockfile sync failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skill_dir_for_key_accepts_skill_folders() {
+        let project = Path::new("/work/project");
+        assert_eq!(
+            skill_dir_for_key(project, ".claude/skills/pdf").unwrap(),
+            project.join(".claude").join("skills").join("pdf"),
+        );
+        assert!(skill_dir_for_key(project, ".cursor/skills/pdf").is_ok());
+    }
+
+    #[test]
+    fn skill_dir_for_key_rejects_other_paths() {
+        let project = Path::new("/work/project");
+        for key in [
+            ".claude/skills",
+            ".claude/skills/pdf/scripts",
+            ".claude/pdf",
+            "src/pdf",
+            "../.claude/skills/pdf",
+            ".claude/skills/../skills/pdf",
+            "/work/project/.claude/skills/pdf",
+        ] {
+            assert!(skill_dir_for_key(project, key).is_err(), "{} was accepted", key);
+        }
+    }
+}
@@ -0,0 +1,649 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::claude_config::{self, TouchedFile};
+use crate::clients;
+use crate::installed;
+use crate::installer;
+use crate::plugin_settings;
+use crate::plugins;
+use crate::remote;
+
+pub const MARKETPLACE_FILE: &str = ".claude-plugin/marketplace.json";
+const KNOWN_MARKETPLACES_FILE: &str = "known_marketplaces.json";
+const MARKETPLACES_DIR: &str = "marketplaces";
+
+// Same shape Claude Code stores in known_marketplaces.json
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum MarketplaceSource {
+    Github {
+        repo: String,
+        #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+    },
+    Git {
+        url: String,
+        #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+    },
+    Directory {
+        path: String,
+    },
+    // A marketplace.json served over HTTP; its plugins need remote sources
+    Url {
+        url: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin_root: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum RemotePluginSource {
+    Github {
+        repo: String,
+        #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+    },
+    Git {
+        url: String,
+        #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+    },
+    // A git URL, or a .zip/.tar.gz archive of the plugin
+    Url {
+        url: String,
+        #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PluginSource {
+    // Relative to the marketplace root (or metadata.pluginRoot when it has no leading "./")
+    Path(String),
+    Remote(RemotePluginSource),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginEntry {
+    pub name: String,
+    pub source: PluginSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    // Whether the plugin must ship its own .claude-plugin/plugin.json (Claude Code's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+    // commands, agents, hooks, mcpServers and the other manifest fields, kept as written
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceManifest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<MarketplaceMetadata>,
+    #[serde(default)]
+    pub plugins: Vec<PluginEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownMarketplace {
+    pub source: MarketplaceSource,
+    pub install_location: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+}
+
+// A marketplace read from disk, ready for plugin lookups
+#[derive(Debug, Clone)]
+pub struct LoadedMarketplace {
+    pub source: MarketplaceSource,
+    pub manifest: MarketplaceManifest,
+    // Folder holding the marketplace; plugin paths are resolved against it
+    pub root: PathBuf,
+}
+
+impl MarketplaceManifest {
+    pub fn plugin(&self, name: &str) -> Result<&PluginEntry, String> {
+        self.plugins.iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("Marketplace {} has no plugin named {}", self.name, name))
+    }
+}
+
+pub fn marketplaces_dir() -> Result<PathBuf, String> {
+    Ok(claude_config::plugins_dir()?.join(MARKETPLACES_DIR))
+}
+
+pub fn known_marketplaces_path() -> Result<PathBuf, String> {
+    Ok(claude_config::plugins_dir()?.join(KNOWN_MARKETPLACES_FILE))
+}
+
+// Accepts what `/plugin marketplace add` accepts: owner/repo, a git URL, a local folder,
+// or the URL of a marketplace.json
+pub fn parse_source(input: &str) -> Result<MarketplaceSource, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Marketplace source is empty".to_string());
+    }
+
+    let is_url = input.starts_with("http://") || input.starts_with("https://");
+    if is_url && input.ends_with(".json") {
+        return Ok(MarketplaceSource::Url { url: input.to_string() });
+    }
+    if is_url || input.starts_with("git@") || input.starts_with("ssh://") || input.starts_with("file://") {
+        return Ok(MarketplaceSource::Git { url: input.to_string(), git_ref: None });
+    }
+
+    let path = clients::expand_home(input)?;
+    if path.exists() {
+        // A bare repository has no working tree to read, so it is cloned like a remote
+        if path.join(MARKETPLACE_FILE).is_file() {
+            return Ok(MarketplaceSource::Directory { path: clients::path_to_string(&path)? });
+        }
+        return Ok(MarketplaceSource::Git { url: clients::path_to_string(&path)?, git_ref: None });
+    }
+
+    let parts: Vec<&str> = input.split('/').collect();
+    if parts.len() == 2 && parts.iter().all(|p| !p.is_empty() && !p.starts_with('.')) {
+        return Ok(MarketplaceSource::Github { repo: input.to_string(), git_ref: None });
+    }
+    Err(format!("Unrecognized marketplace source: {}", input))
+}
+
+pub fn github_url(repo: &str) -> String {
+    format!("https://github.com/{}.git", repo)
+}
+
+pub fn git(args: &[&str], cwd: Option<&Path>) -> Result<String, String> {
+    let mut command = Command::new("git");
+    command.args(args).env("GIT_TERMINAL_PROMPT", "0");
+    if let Some(cwd) = cwd {
+        command.current_dir(cwd);
+    }
+
+    let output = command.output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Shallow clone into `target`, which must not exist yet
+pub fn clone_repo(url: &str, git_ref: Option<&str>, target: &Path) -> Result<(), String> {
+    let target_str = clients::path_to_string(target)?;
+    let mut args = vec!["clone", "--depth", "1"];
+    if let Some(git_ref) = git_ref {
+        args.extend(["--branch", git_ref]);
+    }
+    args.extend(["--", url, target_str.as_str()]);
+    git(&args, None).map(|_| ())
+}
+
+pub fn head_commit(repo: &Path) -> Option<String> {
+    git(&["rev-parse", "HEAD"], Some(repo)).ok()
+}
+
+pub fn read_manifest(root: &Path) -> Result<MarketplaceManifest, String> {
+    let path = root.join(MARKETPLACE_FILE);
+    let bytes = fs::read(&path)
+        .map_err(|_| format!("No {} found in {}", MARKETPLACE_FILE, root.display()))?;
+    parse_manifest(&bytes)
+}
+
+fn parse_manifest(bytes: &[u8]) -> Result<MarketplaceManifest, String> {
+    let manifest: MarketplaceManifest = serde_json::from_slice(bytes)
+        .map_err(|e| format!("Invalid marketplace.json: {}", e))?;
+    clients::validate_skill_name(&manifest.name)
+        .map_err(|_| format!("Invalid marketplace name: {}", manifest.name))?;
+    Ok(manifest)
+}
+
+// Fetches a marketplace into a hidden folder inside `dir`; the caller moves it to its final
+// name once the manifest (and so the name) is known
+fn fetch_to_staging(source: &MarketplaceSource, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create directory: {}", e))?;
+    let staging = installer::sibling_temp_path(&dir.join("marketplace"), "fetch");
+
+    let fetched = match source {
+        MarketplaceSource::Github { repo, git_ref } => clone_repo(&github_url(repo), git_ref.as_deref(), &staging),
+        MarketplaceSource::Git { url, git_ref } => clone_repo(url, git_ref.as_deref(), &staging),
+        MarketplaceSource::Url { url } => remote::fetch_bytes(url).and_then(|bytes| {
+            parse_manifest(&bytes)?;
+            let path = staging.join(MARKETPLACE_FILE);
+            installer::ensure_parent_dir(&path)?;
+            fs::write(&path, bytes).map_err(|e| format!("Failed to write marketplace: {}", e))
+        }),
+        MarketplaceSource::Directory { .. } => Err("Local marketplaces are read in place".to_string()),
+    };
+
+    if let Err(e) = fetched {
+        let _ = installer::remove_path(&staging);
+        return Err(e);
+    }
+    Ok(staging)
+}
+
+fn check_name_free(name: &str, source: &MarketplaceSource) -> Result<(), String> {
+    match find_known(name)? {
+        Some(known) if known.source != *source => Err(format!(
+            "A marketplace named {} is already registered from another source; remove it first",
+            name
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Reads a directory marketplace in place, or downloads a remote one into marketplaces/<name>
+pub fn fetch_marketplace(source: &MarketplaceSource) -> Result<LoadedMarketplace, String> {
+    if let MarketplaceSource::Directory { path } = source {
+        let root = clients::expand_home(path)?;
+        return Ok(LoadedMarketplace { source: source.clone(), manifest: read_manifest(&root)?, root });
+    }
+
+    let staging = fetch_to_staging(source, &marketplaces_dir()?)?;
+    // A marketplace registered under the same name from elsewhere keeps its copy
+    let manifest = read_manifest(&staging).and_then(|manifest| {
+        check_name_free(&manifest.name, source)?;
+        Ok(manifest)
+    });
+    let manifest = match manifest {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let _ = installer::remove_path(&staging);
+            return Err(e);
+        }
+    };
+
+    let root = marketplaces_dir()?.join(&manifest.name);
+    installer::swap_into_place(&staging, &root)?;
+    Ok(LoadedMarketplace { source: source.clone(), manifest, root })
+}
+
+pub fn load_known() -> Result<serde_json::Map<String, Value>, String> {
+    match claude_config::read_json(&known_marketplaces_path()?)? {
+        Some(Value::Object(map)) => Ok(map),
+        Some(_) => Err(format!("{} does not contain a JSON object", KNOWN_MARKETPLACES_FILE)),
+        None => Ok(serde_json::Map::new()),
+    }
+}
+
+pub fn find_known(name: &str) -> Result<Option<KnownMarketplace>, String> {
+    Ok(load_known()?
+        .get(name)
+        .and_then(|value| serde_json::from_value(value.clone()).ok()))
+}
+
+// With `refreshed`, lastUpdated is bumped even when nothing else about the entry changed
+pub fn register(loaded: &LoadedMarketplace, refreshed: bool) -> Result<TouchedFile, String> {
+    let name = loaded.manifest.name.clone();
+    let entry = KnownMarketplace {
+        source: loaded.source.clone(),
+        install_location: clients::path_to_string(&loaded.root)?,
+        last_updated: Some(installed::timestamp_now()),
+    };
+    let value = serde_json::to_value(&entry)
+        .map_err(|e| format!("Failed to serialize marketplace: {}", e))?;
+
+    claude_config::update_json(&known_marketplaces_path()?, vec![name.clone()], move |known| {
+        // Only the fields Skiller manages are replaced; anything else Claude Code keeps there stays
+        match known.get_mut(&name) {
+            Some(Value::Object(existing)) => {
+                if let Value::Object(fields) = value {
+                    let unchanged = !refreshed && fields.iter()
+                        .filter(|(key, _)| key.as_str() != "lastUpdated")
+                        .all(|(key, field)| existing.get(key) == Some(field));
+                    if !unchanged {
+                        existing.extend(fields);
+                    }
+                }
+            }
+            _ => {
+                known.insert(name, value);
+            }
+        }
+        Ok(())
+    })
+}
+
+// Like resolve, but never writes into marketplaces/: a copy that has to be downloaded goes to
+// the system temp folder, and its path is returned so the caller can delete it when done
+pub fn resolve_detached(marketplace: &str) -> Result<(LoadedMarketplace, Option<PathBuf>), String> {
+    let source = match find_known(marketplace)? {
+        Some(known) => {
+            let root = clients::expand_home(&known.install_location)?;
+            if root.join(MARKETPLACE_FILE).is_file() {
+                let manifest = read_manifest(&root)?;
+                return Ok((LoadedMarketplace { source: known.source, manifest, root }, None));
+            }
+            known.source
+        }
+        None => parse_source(marketplace)?,
+    };
+    if let MarketplaceSource::Directory { .. } = source {
+        return Ok((fetch_marketplace(&source)?, None));
+    }
+
+    let staging = fetch_to_staging(&source, &std::env::temp_dir())?;
+    match read_manifest(&staging) {
+        Ok(manifest) => Ok((LoadedMarketplace { source, manifest, root: staging.clone() }, Some(staging))),
+        Err(e) => {
+            let _ = installer::remove_path(&staging);
+            Err(e)
+        }
+    }
+}
+
+// A known marketplace by name, or a new one from a source string
+pub fn resolve(marketplace: &str) -> Result<(LoadedMarketplace, bool), String> {
+    if let Some(known) = find_known(marketplace)? {
+        let root = clients::expand_home(&known.install_location)?;
+        if root.join(MARKETPLACE_FILE).is_file() {
+            let manifest = read_manifest(&root)?;
+            return Ok((LoadedMarketplace { source: known.source, manifest, root }, false));
+        }
+        // Registered but its copy is gone; fetch it again
+        return Ok((fetch_marketplace(&known.source)?, true));
+    }
+
+    Ok((fetch_marketplace(&parse_source(marketplace)?)?, true))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceInfo {
+    pub name: String,
+    pub source: Option<MarketplaceSource>,
+    pub install_location: Option<String>,
+    pub last_updated: Option<String>,
+    pub description: Option<String>,
+    pub plugin_count: usize,
+    // Set when the marketplace is registered but its manifest can't be read
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceChange {
+    pub marketplace: MarketplaceInfo,
+    pub touched: Vec<TouchedFile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplaceRemoval {
+    pub name: String,
+    // The downloaded copy that was deleted; local folders are never removed
+    pub removed_path: Option<String>,
+    pub touched: Vec<TouchedFile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketplacePlugin {
+    pub id: String,
+    pub name: String,
+    pub marketplace: String,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub category: Option<String>,
+    pub installed: bool,
+    pub installed_version: Option<String>,
+    pub enabled: bool,
+}
+
+fn info(name: &str, known: Option<&KnownMarketplace>, manifest: Result<&MarketplaceManifest, String>) -> MarketplaceInfo {
+    let (description, plugin_count, error) = match manifest {
+        Ok(manifest) => (
+            manifest.metadata.as_ref().and_then(|m| m.description.clone()),
+            manifest.plugins.len(),
+            None,
+        ),
+        Err(e) => (None, 0, Some(e)),
+    };
+    MarketplaceInfo {
+        name: name.to_string(),
+        source: known.map(|k| k.source.clone()),
+        install_location: known.map(|k| k.install_location.clone()),
+        last_updated: known.and_then(|k| k.last_updated.clone()),
+        description,
+        plugin_count,
+        error,
+    }
+}
+
+fn loaded_info(loaded: &LoadedMarketplace) -> Result<MarketplaceInfo, String> {
+    let known = find_known(&loaded.manifest.name)?;
+    Ok(info(&loaded.manifest.name, known.as_ref(), Ok(&loaded.manifest)))
+}
+
+fn load_registered(known: &KnownMarketplace) -> Result<LoadedMarketplace, String> {
+    let root = clients::expand_home(&known.install_location)?;
+    Ok(LoadedMarketplace { source: known.source.clone(), manifest: read_manifest(&root)?, root })
+}
+
+// Every marketplace in known_marketplaces.json, with its manifest read from disk
+pub fn list() -> Result<Vec<(MarketplaceInfo, Option<LoadedMarketplace>)>, String> {
+    let mut marketplaces = Vec::new();
+    for (name, value) in load_known()? {
+        let known: Option<KnownMarketplace> = serde_json::from_value(value).ok();
+        let loaded = match &known {
+            Some(known) => load_registered(known),
+            None => Err(format!("{} has an unreadable entry for {}", KNOWN_MARKETPLACES_FILE, name)),
+        };
+        let entry = match loaded {
+            Ok(loaded) => (info(&name, known.as_ref(), Ok(&loaded.manifest)), Some(loaded)),
+            Err(e) => (info(&name, known.as_ref(), Err(e)), None),
+        };
+        marketplaces.push(entry);
+    }
+    Ok(marketplaces)
+}
+
+pub fn add(source: &str) -> Result<MarketplaceChange, String> {
+    let loaded = fetch_marketplace(&parse_source(source)?)?;
+    // Downloaded sources were checked before being moved into place; local ones are read in place
+    check_name_free(&loaded.manifest.name, &loaded.source)?;
+
+    let touched = vec![register(&loaded, true)?];
+    Ok(MarketplaceChange { marketplace: loaded_info(&loaded)?, touched })
+}
+
+pub fn remove(name: &str) -> Result<MarketplaceRemoval, String> {
+    let known = find_known(name)?
+        .ok_or_else(|| format!("Marketplace {} is not registered", name))?;
+
+    let key = name.to_string();
+    let touched = claude_config::update_json(&known_marketplaces_path()?, vec![key.clone()], move |known| {
+        known.remove(&key);
+        Ok(())
+    })?;
+
+    // Only copies Skiller or Claude Code downloaded into marketplaces/ are deleted
+    let location = clients::expand_home(&known.install_location)?;
+    let downloaded = !matches!(known.source, MarketplaceSource::Directory { .. })
+        && location.parent() == Some(marketplaces_dir()?.as_path());
+    let removed_path = if downloaded && fs::symlink_metadata(&location).is_ok() {
+        installer::remove_path(&location)?;
+        Some(known.install_location.clone())
+    } else {
+        None
+    };
+
+    Ok(MarketplaceRemoval { name: name.to_string(), removed_path, touched: vec![touched] })
+}
+
+// Fetches a registered marketplace again. A renamed manifest is refused, since Claude Code
+// keys installed plugins by the marketplace name.
+pub fn refresh(name: &str) -> Result<MarketplaceChange, String> {
+    let known = find_known(name)?
+        .ok_or_else(|| format!("Marketplace {} is not registered", name))?;
+    let loaded = fetch_marketplace(&known.source)?;
+    if loaded.manifest.name != name {
+        return Err(format!("Marketplace {} now calls itself {}", name, loaded.manifest.name));
+    }
+
+    let touched = vec![register(&loaded, true)?];
+    Ok(MarketplaceChange { marketplace: loaded_info(&loaded)?, touched })
+}
+
+// One list over every registered marketplace, each plugin tagged with where it comes from
+// and whether Claude Code has it installed and enabled
+pub fn list_plugins(project_path: Option<&str>) -> Result<Vec<MarketplacePlugin>, String> {
+    let settings = plugin_settings::list(project_path)?;
+    let mut plugins = Vec::new();
+
+    for (_, loaded) in list()? {
+        let Some(loaded) = loaded else {
+            continue;
+        };
+        for entry in &loaded.manifest.plugins {
+            let id = plugins::plugin_id(&entry.name, &loaded.manifest.name);
+            let setting = settings.iter().find(|s| s.id == id);
+            plugins.push(MarketplacePlugin {
+                name: entry.name.clone(),
+                marketplace: loaded.manifest.name.clone(),
+                description: entry.description.clone(),
+                version: entry.version.clone(),
+                category: entry.extra.get("category").and_then(Value::as_str).map(|c| c.to_string()),
+                installed: setting.map(|s| s.installed).unwrap_or(false),
+                installed_version: setting.and_then(|s| s.version.clone()),
+                enabled: setting.map(|s| s.enabled).unwrap_or(false),
+                id,
+            });
+        }
+    }
+
+    plugins.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.marketplace.cmp(&b.marketplace)));
+    Ok(plugins)
+}
+
+#[tauri::command]
+pub async fn list_marketplaces() -> Result<Vec<MarketplaceInfo>, String> {
+    tauri::async_runtime::spawn_blocking(|| Ok(list()?.into_iter().map(|(info, _)| info).collect()))
+        .await
+        .map_err(|e| format!("Listing marketplaces failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn add_marketplace(source: String) -> Result<MarketplaceChange, String> {
+    tauri::async_runtime::spawn_blocking(move || add(&source))
+        .await
+        .map_err(|e| format!("Adding marketplace failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn remove_marketplace(name: String) -> Result<MarketplaceRemoval, String> {
+    tauri::async_runtime::spawn_blocking(move || remove(&name))
+        .await
+        .map_err(|e| format!("Removing marketplace failed: {}", e))?
+}
+
+// Refreshes one marketplace, or all of them; failures are reported per marketplace
+#[tauri::command]
+pub async fn refresh_marketplaces(name: Option<String>) -> Result<Vec<MarketplaceInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let names: Vec<String> = match name {
+            Some(name) => vec![name],
+            None => load_known()?.keys().cloned().collect(),
+        };
+
+        let mut results = Vec::new();
+        for name in names {
+            match refresh(&name) {
+                Ok(change) => results.push(change.marketplace),
+                Err(e) => {
+                    let known = find_known(&name).ok().flatten();
+                    results.push(info(&name, known.as_ref(), Err(e)));
+                }
+            }
+        }
+        Ok(results)
+    })
+    .await
+    .map_err(|e| format!("Refreshing marketplaces failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn list_marketplace_plugins(project_path: Option<String>) -> Result<Vec<MarketplacePlugin>, String> {
+    tauri::async_runtime::spawn_blocking(move || list_plugins(project_path.as_deref()))
+        .await
+        .map_err(|e| format!("Listing marketplace plugins failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_source_recognizes_remote_sources() {
+        assert_eq!(
+            parse_source("anthropics/claude-code").unwrap(),
+            MarketplaceSource::Github { repo: "anthropics/claude-code".to_string(), git_ref: None },
+        );
+        assert_eq!(
+            parse_source(" https://example.com/repo.git ").unwrap(),
+            MarketplaceSource::Git { url: "https://example.com/repo.git".to_string(), git_ref: None },
+        );
+        assert_eq!(
+            parse_source("git@github.com:acme/plugins.git").unwrap(),
+            MarketplaceSource::Git { url: "git@github.com:acme/plugins.git".to_string(), git_ref: None },
+        );
+        assert_eq!(
+            parse_source("https://example.com/marketplace.json").unwrap(),
+            MarketplaceSource::Url { url: "https://example.com/marketplace.json".to_string() },
+        );
+    }
+
+    #[test]
+    fn parse_source_tells_folders_from_repositories() {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-parse-source", std::process::id()));
+        let path = clients::path_to_string(&dir).unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        let repository = parse_source(&path);
+
+        fs::create_dir_all(dir.join(".claude-plugin")).unwrap();
+        fs::write(dir.join(MARKETPLACE_FILE), "{}").unwrap();
+        let folder = parse_source(&path);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(repository.unwrap(), MarketplaceSource::Git { url: path.clone(), git_ref: None });
+        assert_eq!(folder.unwrap(), MarketplaceSource::Directory { path });
+    }
+
+    #[test]
+    fn parse_source_rejects_everything_else() {
+        for input in ["", "   ", "acme", "acme/plugins/extra", "../plugins", "acme/"] {
+            assert!(parse_source(input).is_err(), "{:?} was accepted", input);
+        }
+    }
+}
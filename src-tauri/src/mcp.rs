@@ -0,0 +1,754 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::claude_config::{self, TouchedFile};
+use crate::clients::{self, Client};
+use crate::lint::Severity;
+use crate::plugin_settings::{self, SettingsScope};
+
+const DISABLED_SERVERS: &str = "disabledMcpServers";
+const ENABLED_MCPJSON: &str = "enabledMcpjsonServers";
+const DISABLED_MCPJSON: &str = "disabledMcpjsonServers";
+
+// Apps started from the Dock or a desktop launcher get a minimal PATH; these are where
+// package managers usually put the tools MCP servers are started with
+const EXTRA_BIN_DIRS: [&str; 4] = ["/opt/homebrew/bin", "/usr/local/bin", "/usr/bin", "/bin"];
+const EXTRA_HOME_BIN_DIRS: [&str; 5] = [".local/bin", ".cargo/bin", ".bun/bin", ".volta/bin", ".deno/bin"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpIssue {
+    pub severity: Severity,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServerEntry {
+    pub name: String,
+    pub client: Client,
+    pub scope: SettingsScope,
+    // Config file the server is defined in
+    pub path: String,
+    pub transport: String,
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    pub url: Option<String>,
+    // Only the variable names; values can hold secrets
+    pub env: Vec<String>,
+    pub enabled: bool,
+    pub issues: Vec<McpIssue>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpChange {
+    pub touched: TouchedFile,
+    pub server: Option<McpServerEntry>,
+}
+
+// One place a client keeps MCP servers: a JSON file and the object path to the servers in it
+struct McpConfig {
+    client: Client,
+    scope: SettingsScope,
+    path: PathBuf,
+    pointer: Vec<String>,
+    // Where the client starts servers from, when known
+    project: Option<PathBuf>,
+}
+
+// Claude Code keys its ~/.claude.json project entries by the resolved working directory,
+// so symlinks (macOS's /var -> /private/var) are followed and trailing separators dropped
+fn project_key(project: &Path) -> String {
+    let resolved = fs::canonicalize(project).unwrap_or_else(|_| project.to_path_buf());
+    let key = resolved.to_string_lossy();
+    // Windows' canonical form starts with the verbatim \\?\ prefix, which Claude Code doesn't use
+    let key = key.strip_prefix(r"\\?\").unwrap_or(&key);
+    match key.trim_end_matches(['/', '\\']) {
+        "" => key.to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+// Every config file Skiller knows how to read MCP servers from, for the given project
+fn configs(project_path: Option<&str>) -> Result<Vec<McpConfig>, String> {
+    let home = clients::home_dir()?;
+    let project = project_path.map(clients::expand_home).transpose()?;
+    let servers = |key: &str| vec![key.to_string()];
+    let mut configs = vec![
+        McpConfig {
+            client: Client::ClaudeCode,
+            scope: SettingsScope::User,
+            path: claude_config::user_state_path()?,
+            pointer: servers("mcpServers"),
+            project: project.clone(),
+        },
+        McpConfig {
+            client: Client::Claude,
+            scope: SettingsScope::User,
+            path: dirs::config_dir()
+                .unwrap_or_else(|| home.join(".config"))
+                .join("Claude")
+                .join("claude_desktop_config.json"),
+            pointer: servers("mcpServers"),
+            project: None,
+        },
+        McpConfig {
+            client: Client::Cursor,
+            scope: SettingsScope::User,
+            path: home.join(".cursor").join("mcp.json"),
+            pointer: servers("mcpServers"),
+            project: project.clone(),
+        },
+    ];
+
+    if let Some(project) = project {
+        configs.extend([
+            McpConfig {
+                client: Client::ClaudeCode,
+                scope: SettingsScope::Local,
+                path: claude_config::user_state_path()?,
+                pointer: vec!["projects".to_string(), project_key(&project), "mcpServers".to_string()],
+                project: Some(project.clone()),
+            },
+            McpConfig {
+                client: Client::ClaudeCode,
+                scope: SettingsScope::Project,
+                path: project.join(".mcp.json"),
+                pointer: servers("mcpServers"),
+                project: Some(project.clone()),
+            },
+            McpConfig {
+                client: Client::Cursor,
+                scope: SettingsScope::Project,
+                path: project.join(".cursor").join("mcp.json"),
+                pointer: servers("mcpServers"),
+                project: Some(project.clone()),
+            },
+            McpConfig {
+                client: Client::Vscode,
+                scope: SettingsScope::Project,
+                path: project.join(".vscode").join("mcp.json"),
+                pointer: servers("servers"),
+                project: Some(project.clone()),
+            },
+        ]);
+    }
+    Ok(configs)
+}
+
+fn find_config(target: Client, scope: SettingsScope, project_path: Option<&str>) -> Result<McpConfig, String> {
+    if scope != SettingsScope::User && project_path.is_none() {
+        return Err("Project and local MCP servers need a project path".to_string());
+    }
+    configs(project_path)?
+        .into_iter()
+        .find(|c| c.client == target && c.scope == scope)
+        .ok_or_else(|| format!("{} has no MCP configuration at that scope", target.as_str()))
+}
+
+fn lookup<'a>(root: &'a Value, pointer: &[String]) -> Option<&'a serde_json::Map<String, Value>> {
+    pointer.iter()
+        .try_fold(root, |value, key| value.get(key))
+        .and_then(Value::as_object)
+}
+
+// The servers object inside `root`, created along the way if it isn't there yet
+fn lookup_mut<'a>(
+    root: &'a mut serde_json::Map<String, Value>,
+    pointer: &[String],
+) -> Result<&'a mut serde_json::Map<String, Value>, String> {
+    let mut current = root;
+    for key in pointer {
+        current = current.entry(key.clone())
+            .or_insert_with(|| Value::Object(Default::default()))
+            .as_object_mut()
+            .ok_or_else(|| format!("{} is not a JSON object", key))?;
+    }
+    Ok(current)
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value.and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).map(|s| s.to_string()).collect())
+        .unwrap_or_default()
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(|v| v.to_string())
+}
+
+// Directories searched for a server's command: PATH first, then the usual install locations
+fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    if let Ok(home) = clients::home_dir() {
+        dirs.extend(EXTRA_HOME_BIN_DIRS.iter().map(|d| home.join(d)));
+    }
+    dirs.extend(EXTRA_BIN_DIRS.iter().map(PathBuf::from));
+    dirs
+}
+
+// Whether a server's command can be found. Relative paths are resolved against the project,
+// where the client starts the server; without a project there's no telling (None).
+fn command_exists(command: &str, project: Option<&Path>) -> Option<bool> {
+    if command.contains('/') || command.contains('\\') {
+        let path = Path::new(command);
+        return match project {
+            _ if path.is_absolute() => Some(path.is_file()),
+            Some(project) => Some(project.join(path).is_file()),
+            None => None,
+        };
+    }
+
+    let extensions: &[&str] = if cfg!(windows) { &["", ".exe", ".cmd", ".bat"] } else { &[""] };
+    Some(search_dirs()
+        .into_iter()
+        .flat_map(|dir| extensions.iter().map(move |ext| dir.join(format!("{}{}", command, ext))))
+        .any(|path| path.is_file()))
+}
+
+// VS Code and Cursor substitute ${workspaceFolder} and ${userHome} themselves
+fn expand_workspace(value: &str, client: Client, project: Option<&Path>) -> String {
+    if !matches!(client, Client::Vscode | Client::Cursor) {
+        return value.to_string();
+    }
+    let mut expanded = value.to_string();
+    if let Some(project) = project {
+        expanded = expanded.replace("${workspaceFolder}", &project.to_string_lossy());
+    }
+    if let Ok(home) = clients::home_dir() {
+        expanded = expanded.replace("${userHome}", &home.to_string_lossy());
+    }
+    expanded
+}
+
+// Environment variables a value reads. ${env:VAR} works in every client; Claude Code also
+// expands plain ${VAR} (skipped when it has a ${VAR:-default}). ${input:...} prompts and
+// VS Code's and Cursor's own variables aren't environment lookups.
+fn env_references(value: &str, client: Client) -> Vec<String> {
+    let editor = matches!(client, Client::Vscode | Client::Cursor);
+    let mut names = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let inner = &after[..end];
+        rest = &after[end + 1..];
+
+        if let Some(name) = inner.strip_prefix("env:") {
+            names.push(name.to_string());
+        } else if !editor && !inner.contains(':') && !inner.is_empty() {
+            names.push(inner.to_string());
+        }
+    }
+    names
+}
+
+fn check_references(value: &str, field: &str, client: Client, issues: &mut Vec<McpIssue>) {
+    for name in env_references(value, client) {
+        if std::env::var_os(&name).is_none() {
+            issues.push(McpIssue {
+                severity: Severity::Warning,
+                field: Some(field.to_string()),
+                message: format!("{} refers to {}, which isn't set", field, name),
+            });
+        }
+    }
+}
+
+// Problems that would keep a server from starting: a missing command, a bad URL, or
+// environment variables that are empty, placeholders or refer to unset variables
+pub fn validate(config: &Value, client: Client, project: Option<&Path>) -> Vec<McpIssue> {
+    let mut issues = Vec::new();
+    let error = |field: Option<&str>, message: String| McpIssue {
+        severity: Severity::Error,
+        field: field.map(|f| f.to_string()),
+        message,
+    };
+    if !config.is_object() {
+        return vec![error(None, "Server entry must be a JSON object".to_string())];
+    }
+
+    let transport = transport(config);
+    if transport == "stdio" {
+        match text(config, "command").map(|c| expand_workspace(&c, client, project)) {
+            None => issues.push(error(Some("command"), "Server has neither a command nor a url".to_string())),
+            // Still holds variables the client fills in at launch, so only those are checked
+            Some(command) if command.contains("${") => check_references(&command, "command", client, &mut issues),
+            Some(command) => {
+                if command_exists(&command, project) == Some(false) {
+                    let message = if command.contains('/') || command.contains('\\') {
+                        format!("{} does not exist", command)
+                    } else {
+                        format!("{} was not found on PATH", command)
+                    };
+                    issues.push(error(Some("command"), message));
+                }
+            }
+        }
+        for arg in string_list(config.get("args")) {
+            check_references(&arg, "args", client, &mut issues);
+        }
+    } else {
+        match text(config, "url") {
+            None => issues.push(error(Some("url"), format!("A {} server needs a url", transport))),
+            Some(url) if !url.starts_with("http://") && !url.starts_with("https://") && !url.contains("${") => {
+                issues.push(error(Some("url"), format!("{} is not an http(s) URL", url)));
+            }
+            Some(url) => check_references(&url, "url", client, &mut issues),
+        }
+    }
+
+    for (field, key) in [("env", "env"), ("headers", "headers")] {
+        let Some(values) = config.get(key).and_then(Value::as_object) else {
+            continue;
+        };
+        for (name, value) in values {
+            let field = format!("{}.{}", field, name);
+            let value = value.as_str().unwrap_or_default().trim();
+            let placeholder = value.starts_with('<') && value.ends_with('>');
+            if value.is_empty() || placeholder {
+                issues.push(McpIssue {
+                    severity: Severity::Warning,
+                    field: Some(field.clone()),
+                    message: format!("{} is {}", field, if placeholder { "still a placeholder" } else { "empty" }),
+                });
+            }
+            check_references(value, &field, client, &mut issues);
+        }
+    }
+    issues
+}
+
+fn transport(config: &Value) -> String {
+    match text(config, "type") {
+        Some(kind) => kind,
+        None if config.get("url").is_some() => "http".to_string(),
+        None => "stdio".to_string(),
+    }
+}
+
+// Servers Claude Code has been told to skip for this project: /mcp writes disabledMcpServers
+// into the project's entry in ~/.claude.json, and .mcp.json servers can also be turned off
+// with disabledMcpjsonServers in any settings file
+struct ClaudeCodeToggles {
+    disabled: Vec<String>,
+    disabled_mcpjson: Vec<String>,
+}
+
+fn claude_code_toggles(project_path: Option<&str>) -> Result<ClaudeCodeToggles, String> {
+    let mut toggles = ClaudeCodeToggles { disabled: Vec::new(), disabled_mcpjson: Vec::new() };
+    let Some(project) = project_path else {
+        return Ok(toggles);
+    };
+
+    let key = project_key(&clients::expand_home(project)?);
+    if let Some(state) = claude_config::read_json(&claude_config::user_state_path()?)? {
+        if let Some(entry) = state.get("projects").and_then(|p| p.get(&key)) {
+            toggles.disabled = string_list(entry.get(DISABLED_SERVERS));
+            toggles.disabled_mcpjson = string_list(entry.get(DISABLED_MCPJSON));
+        }
+    }
+    for scope in [SettingsScope::User, SettingsScope::Project, SettingsScope::Local] {
+        let path = plugin_settings::settings_path(scope, project_path)?;
+        if let Some(settings) = claude_config::read_json(&path)? {
+            toggles.disabled_mcpjson.extend(string_list(settings.get(DISABLED_MCPJSON)));
+        }
+    }
+    Ok(toggles)
+}
+
+fn entry(config: &McpConfig, name: &str, server: &Value, toggles: &ClaudeCodeToggles) -> Result<McpServerEntry, String> {
+    let enabled = match (config.client, config.scope) {
+        (Client::ClaudeCode, SettingsScope::Project) => !toggles.disabled_mcpjson.iter().any(|n| n == name),
+        (Client::ClaudeCode, _) => !toggles.disabled.iter().any(|n| n == name),
+        _ => true,
+    };
+    Ok(McpServerEntry {
+        name: name.to_string(),
+        client: config.client,
+        scope: config.scope,
+        path: clients::path_to_string(&config.path)?,
+        transport: transport(server),
+        command: text(server, "command"),
+        args: string_list(server.get("args")),
+        url: text(server, "url"),
+        env: server.get("env").and_then(Value::as_object)
+            .map(|env| env.keys().cloned().collect())
+            .unwrap_or_default(),
+        enabled,
+        issues: validate(server, config.client, config.project.as_deref()),
+    })
+}
+
+pub fn list(project_path: Option<&str>) -> Result<Vec<McpServerEntry>, String> {
+    let toggles = claude_code_toggles(project_path)?;
+    let mut servers = Vec::new();
+    for config in configs(project_path)? {
+        let Some(root) = claude_config::read_json(&config.path)? else {
+            continue;
+        };
+        let Some(found) = lookup(&root, &config.pointer) else {
+            continue;
+        };
+        for (name, server) in found {
+            servers.push(entry(&config, name, server, &toggles)?);
+        }
+    }
+    Ok(servers)
+}
+
+fn touched_key(config: &McpConfig, name: &str) -> String {
+    let mut key = config.pointer.clone();
+    key.push(name.to_string());
+    key.join(".")
+}
+
+pub fn add(
+    target: Client,
+    scope: SettingsScope,
+    project_path: Option<&str>,
+    name: &str,
+    server: Value,
+    overwrite: bool,
+) -> Result<McpChange, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().any(|c| c.is_control()) {
+        return Err(format!("Invalid MCP server name: {:?}", name));
+    }
+    if !server.is_object() {
+        return Err("Server entry must be a JSON object".to_string());
+    }
+
+    let config = find_config(target, scope, project_path)?;
+    let key = touched_key(&config, name);
+    let pointer = config.pointer.clone();
+    let server_name = name.to_string();
+    let value = server.clone();
+    let touched = claude_config::update_json(&config.path, vec![key], move |root| {
+        let servers = lookup_mut(root, &pointer)?;
+        if servers.contains_key(&server_name) && !overwrite {
+            return Err(format!("An MCP server named {} already exists", server_name));
+        }
+        servers.insert(server_name, value);
+        Ok(())
+    })?;
+
+    let toggles = claude_code_toggles(project_path)?;
+    Ok(McpChange { touched, server: Some(entry(&config, name, &server, &toggles)?) })
+}
+
+pub fn remove(target: Client, scope: SettingsScope, project_path: Option<&str>, name: &str) -> Result<McpChange, String> {
+    let config = find_config(target, scope, project_path)?;
+    let exists = claude_config::read_json(&config.path)?
+        .map(|root| lookup(&root, &config.pointer).map(|s| s.contains_key(name)).unwrap_or(false))
+        .unwrap_or(false);
+    if !exists {
+        return Err(format!("No MCP server named {} in {}", name, config.path.display()));
+    }
+
+    let pointer = config.pointer.clone();
+    let server_name = name.to_string();
+    let touched = claude_config::update_json(&config.path, vec![touched_key(&config, name)], move |root| {
+        lookup_mut(root, &pointer)?.remove(&server_name);
+        Ok(())
+    })?;
+    Ok(McpChange { touched, server: None })
+}
+
+fn toggle_list(object: &mut serde_json::Map<String, Value>, list: &str, name: &str, present: bool) -> Result<(), String> {
+    let items = object.entry(list.to_string())
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| format!("{} is not a list", list))?;
+    items.retain(|item| item.as_str() != Some(name));
+    if present {
+        items.push(Value::from(name));
+    }
+    Ok(())
+}
+
+// Only Claude Code has a per-project switch for servers; other clients have to remove them
+pub fn set_enabled(
+    target: Client,
+    scope: SettingsScope,
+    project_path: Option<&str>,
+    name: &str,
+    enabled: bool,
+) -> Result<McpChange, String> {
+    if target != Client::ClaudeCode {
+        return Err(format!("{} has no setting to disable an MCP server; remove it instead", target.as_str()));
+    }
+    let project = project_path
+        .ok_or_else(|| "Claude Code turns MCP servers on and off per project; pass a project path".to_string())?;
+    let config = find_config(target, scope, project_path)?;
+    let server = claude_config::read_json(&config.path)?
+        .and_then(|root| lookup(&root, &config.pointer).and_then(|s| s.get(name).cloned()))
+        .ok_or_else(|| format!("No MCP server named {} in {}", name, config.path.display()))?;
+
+    let project_key = project_key(&clients::expand_home(project)?);
+    let server_name = name.to_string();
+    let touched = if scope == SettingsScope::Project {
+        // .mcp.json servers are switched in the project's local settings, where Claude Code
+        // also records approvals
+        let path = plugin_settings::settings_path(SettingsScope::Local, project_path)?;
+        let key = format!("{}.{}", if enabled { ENABLED_MCPJSON } else { DISABLED_MCPJSON }, name);
+        claude_config::update_json(&path, vec![key], move |settings| {
+            toggle_list(settings, DISABLED_MCPJSON, &server_name, !enabled)?;
+            if enabled || settings.contains_key(ENABLED_MCPJSON) {
+                toggle_list(settings, ENABLED_MCPJSON, &server_name, enabled)?;
+            }
+            Ok(())
+        })?
+    } else {
+        let key = format!("projects.{}.{}.{}", project_key, DISABLED_SERVERS, name);
+        claude_config::update_json(&claude_config::user_state_path()?, vec![key], move |state| {
+            let entry = lookup_mut(state, &["projects".to_string(), project_key])?;
+            toggle_list(entry, DISABLED_SERVERS, &server_name, !enabled)
+        })?
+    };
+
+    let toggles = claude_code_toggles(project_path)?;
+    Ok(McpChange { touched, server: Some(entry(&config, name, &server, &toggles)?) })
+}
+
+#[tauri::command]
+pub async fn list_mcp_servers(project_path: Option<String>) -> Result<Vec<McpServerEntry>, String> {
+    tauri::async_runtime::spawn_blocking(move || list(project_path.as_deref()))
+        .await
+        .map_err(|e| format!("Listing MCP servers failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn add_mcp_server(
+    client: Client,
+    scope: SettingsScope,
+    project_path: Option<String>,
+    name: String,
+    server: Value,
+    overwrite: Option<bool>,
+) -> Result<McpChange, String> {
+    let overwrite = overwrite.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || add(client, scope, project_path.as_deref(), &name, server, overwrite))
+        .await
+        .map_err(|e| format!("Adding MCP server failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn remove_mcp_server(
+    client: Client,
+    scope: SettingsScope,
+    project_path: Option<String>,
+    name: String,
+) -> Result<McpChange, String> {
+    tauri::async_runtime::spawn_blocking(move || remove(client, scope, project_path.as_deref(), &name))
+        .await
+        .map_err(|e| format!("Removing MCP server failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn set_mcp_server_enabled(
+    client: Client,
+    scope: SettingsScope,
+    project_path: Option<String>,
+    name: String,
+    enabled: bool,
+) -> Result<McpChange, String> {
+    tauri::async_runtime::spawn_blocking(move || set_enabled(client, scope, project_path.as_deref(), &name, enabled))
+        .await
+        .map_err(|e| format!("Updating MCP server failed: {}", e))?
+}
+
+// Checks an entry before it is added; client decides which ${...} syntax applies
+#[tauri::command]
+pub async fn validate_mcp_server(
+    server: Value,
+    client: Option<Client>,
+    project_path: Option<String>,
+) -> Result<Vec<McpIssue>, String> {
+    let client = client.unwrap_or(Client::ClaudeCode);
+    tauri::async_runtime::spawn_blocking(move || {
+        let project = project_path.map(|p| clients::expand_home(&p)).transpose()?;
+        Ok(validate(&server, client, project.as_deref()))
+    })
+        .await
+        .map_err(|e| format!("Validating MCP server failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn messages(issues: &[McpIssue]) -> Vec<(Option<&str>, &str)> {
+        issues.iter().map(|i| (i.field.as_deref(), i.message.as_str())).collect()
+    }
+
+    #[test]
+    fn env_references_follow_each_clients_syntax() {
+        let value = "${env:TOKEN} ${HOME_DIR} ${PORT:-80} ${input:key} ${} ${workspaceFolder}/x ${UNCLOSED";
+        assert_eq!(env_references(value, Client::ClaudeCode), ["TOKEN", "HOME_DIR", "workspaceFolder"]);
+        assert_eq!(env_references(value, Client::Vscode), ["TOKEN"]);
+        assert_eq!(env_references(value, Client::Cursor), ["TOKEN"]);
+        assert!(env_references("no variables", Client::ClaudeCode).is_empty());
+    }
+
+    #[test]
+    fn command_exists_resolves_paths_against_the_project() {
+        let project = temp_dir("mcp-command");
+        fs::create_dir_all(project.join("bin")).unwrap();
+        fs::write(project.join("bin/server"), "").unwrap();
+        let absolute = project.join("bin/server");
+
+        let found = [
+            command_exists(absolute.to_str().unwrap(), None),
+            command_exists("./bin/server", Some(&project)),
+            command_exists("./bin/missing", Some(&project)),
+            command_exists("./bin/server", None),
+        ];
+        let _ = fs::remove_dir_all(&project);
+
+        assert_eq!(found, [Some(true), Some(true), Some(false), None]);
+        assert_eq!(command_exists("skiller-no-such-command", None), Some(false));
+        #[cfg(unix)]
+        assert_eq!(command_exists("sh", None), Some(true));
+    }
+
+    #[test]
+    fn validate_reports_missing_commands_and_bad_urls() {
+        let issues = |server: Value| validate(&server, Client::ClaudeCode, None);
+
+        assert_eq!(messages(&issues(json!("npx"))), [(None, "Server entry must be a JSON object")]);
+        assert_eq!(messages(&issues(json!({}))), [(Some("command"), "Server has neither a command nor a url")]);
+        assert_eq!(
+            messages(&issues(json!({"command": "skiller-no-such-command"}))),
+            [(Some("command"), "skiller-no-such-command was not found on PATH")],
+        );
+        assert_eq!(
+            messages(&issues(json!({"command": "/skiller/no/such/server"}))),
+            [(Some("command"), "/skiller/no/such/server does not exist")],
+        );
+        assert_eq!(messages(&issues(json!({"type": "sse"}))), [(Some("url"), "A sse server needs a url")]);
+        assert_eq!(
+            messages(&issues(json!({"url": "ftp://example.com"}))),
+            [(Some("url"), "ftp://example.com is not an http(s) URL")],
+        );
+        assert!(issues(json!({"type": "http", "url": "https://example.com/mcp"})).is_empty());
+        #[cfg(unix)]
+        assert!(issues(json!({"command": "sh", "args": ["-c", "true"]})).is_empty());
+    }
+
+    #[test]
+    fn validate_warns_about_empty_placeholder_and_unset_values() {
+        let server = json!({
+            "url": "https://example.com/${SKILLER_TEST_UNSET_PATH}",
+            "headers": {"Authorization": "Bearer ${env:SKILLER_TEST_UNSET_TOKEN}"},
+            "env": {"EMPTY": " ", "KEY": "<your key>"},
+        });
+        let issues = validate(&server, Client::ClaudeCode, None);
+
+        assert!(issues.iter().all(|i| i.severity == Severity::Warning));
+        assert_eq!(messages(&issues), [
+            (Some("url"), "url refers to SKILLER_TEST_UNSET_PATH, which isn't set"),
+            (Some("env.EMPTY"), "env.EMPTY is empty"),
+            (Some("env.KEY"), "env.KEY is still a placeholder"),
+            (Some("headers.Authorization"), "headers.Authorization refers to SKILLER_TEST_UNSET_TOKEN, which isn't set"),
+        ]);
+        // Only Claude Code reads a plain ${VAR}
+        assert!(validate(&json!({"url": "https://example.com/${SKILLER_TEST_UNSET_PATH}"}), Client::Cursor, None).is_empty());
+    }
+
+    #[test]
+    fn validate_expands_the_editors_workspace_folder() {
+        let project = temp_dir("mcp-workspace");
+        fs::write(project.join("server.sh"), "").unwrap();
+        let present = validate(&json!({"command": "${workspaceFolder}/server.sh"}), Client::Vscode, Some(&project));
+        let missing = validate(&json!({"command": "${workspaceFolder}/gone.sh"}), Client::Cursor, Some(&project));
+        let _ = fs::remove_dir_all(&project);
+
+        assert!(present.is_empty());
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].message.ends_with("gone.sh does not exist"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn project_key_resolves_symlinks_and_trailing_slashes() {
+        let dir = temp_dir("mcp-project-key");
+        fs::create_dir_all(dir.join("private/app")).unwrap();
+        std::os::unix::fs::symlink(dir.join("private"), dir.join("var")).unwrap();
+        let resolved = fs::canonicalize(dir.join("private/app")).unwrap().to_string_lossy().to_string();
+
+        let through_link = project_key(&dir.join("var/app/"));
+        let missing = project_key(Path::new("/skiller/no/such/project/"));
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(through_link, resolved);
+        assert_eq!(missing, "/skiller/no/such/project");
+        assert_eq!(project_key(Path::new("/")), "/");
+    }
+
+    #[test]
+    fn set_enabled_edits_only_the_projects_toggle_lists() {
+        let _lock = claude_config::CONFIG_DIR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let root = temp_dir("mcp-set-enabled");
+        let project = root.join("app");
+        fs::create_dir_all(&project).unwrap();
+        let key = project_key(&project);
+        let state_path = root.join("config/.claude.json");
+        fs::create_dir_all(root.join("config")).unwrap();
+        let state = json!({
+            "numStartups": 3,
+            "projects": {
+                key.clone(): {"allowedTools": ["Bash"], "mcpServers": {"db": {"command": "db-server"}}},
+                "/elsewhere": {"disabledMcpServers": ["db"]},
+            },
+        });
+        fs::write(&state_path, serde_json::to_string_pretty(&state).unwrap()).unwrap();
+        fs::write(project.join(".mcp.json"), r#"{"mcpServers": {"files": {"command": "files-server"}}}"#).unwrap();
+        std::env::set_var("CLAUDE_CONFIG_DIR", root.join("config"));
+
+        // A trailing slash still lands on the same project entry
+        let project_path = format!("{}/", project.display());
+        let disabled = set_enabled(Client::ClaudeCode, SettingsScope::Local, Some(&project_path), "db", false);
+        let after_disable = claude_config::read_json(&state_path).unwrap().unwrap();
+        let enabled = set_enabled(Client::ClaudeCode, SettingsScope::Local, Some(&project_path), "db", true);
+        let after_enable = claude_config::read_json(&state_path).unwrap().unwrap();
+
+        let mcpjson_off = set_enabled(Client::ClaudeCode, SettingsScope::Project, Some(&project_path), "files", false);
+        let local_off = claude_config::read_json(&project.join(".claude/settings.local.json")).unwrap().unwrap();
+        let mcpjson_on = set_enabled(Client::ClaudeCode, SettingsScope::Project, Some(&project_path), "files", true);
+        let local_on = claude_config::read_json(&project.join(".claude/settings.local.json")).unwrap().unwrap();
+        let other_client = set_enabled(Client::Cursor, SettingsScope::Project, Some(&project_path), "files", false);
+
+        std::env::remove_var("CLAUDE_CONFIG_DIR");
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(!disabled.unwrap().server.unwrap().enabled);
+        assert_eq!(after_disable["projects"][&key]["disabledMcpServers"], json!(["db"]));
+        assert!(enabled.unwrap().server.unwrap().enabled);
+        let mut expected = state.clone();
+        expected["projects"][&key]["disabledMcpServers"] = json!([]);
+        assert_eq!(after_enable, expected);
+
+        assert!(!mcpjson_off.unwrap().server.unwrap().enabled);
+        assert_eq!(local_off, json!({"disabledMcpjsonServers": ["files"]}));
+        assert!(mcpjson_on.unwrap().server.unwrap().enabled);
+        assert_eq!(local_on, json!({"disabledMcpjsonServers": [], "enabledMcpjsonServers": ["files"]}));
+        assert!(other_client.is_err());
+    }
+}
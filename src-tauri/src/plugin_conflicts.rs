@@ -0,0 +1,523 @@
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+use crate::claude_config;
+use crate::clients;
+use crate::plugin_inspect::{self, HookInfo, PluginInventory};
+use crate::plugin_settings::{self, SettingsScope};
+use crate::plugins;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DefinitionKind {
+    Command,
+    Agent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProviderKind {
+    Plugin,
+    // ~/.claude/commands, ~/.claude/agents and the user settings file
+    Personal,
+    // <project>/.claude/... and the project's settings files
+    Project,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Provider {
+    pub kind: ProviderKind,
+    // Plugin id, or "personal" / "project"
+    pub source: String,
+    pub file: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameCollision {
+    pub kind: DefinitionKind,
+    pub name: String,
+    pub providers: Vec<Provider>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookEntry {
+    pub provider: Provider,
+    pub matcher: Option<String>,
+    pub command: Option<String>,
+}
+
+// Hooks from different sources that fire on the same event for overlapping tools
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookOverlap {
+    pub event: String,
+    pub entries: Vec<HookEntry>,
+}
+
+// An installed plugin whose files couldn't be read, so it wasn't compared with the others
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UncheckedPlugin {
+    pub id: String,
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginConflictReport {
+    // Set when the report only covers conflicts with a plugin about to be installed
+    pub candidate: Option<String>,
+    pub collisions: Vec<NameCollision>,
+    pub hook_overlaps: Vec<HookOverlap>,
+    pub unchecked: Vec<UncheckedPlugin>,
+    // Set when the installed setup couldn't be read, so nothing was checked
+    pub error: Option<String>,
+}
+
+impl PluginConflictReport {
+    pub fn unchecked(candidate: &str, error: String) -> Self {
+        PluginConflictReport {
+            candidate: Some(candidate.to_string()),
+            collisions: Vec::new(),
+            hook_overlaps: Vec::new(),
+            unchecked: Vec::new(),
+            error: Some(error),
+        }
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        !self.collisions.is_empty() || !self.hook_overlaps.is_empty()
+    }
+
+    // e.g. "command review, hooks on PreToolUse"
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = self.collisions.iter()
+            .map(|c| format!("{} {}", if c.kind == DefinitionKind::Command { "command" } else { "agent" }, c.name))
+            .collect();
+        parts.extend(self.hook_overlaps.iter().map(|o| format!("hooks on {}", o.event)));
+        parts.join(", ")
+    }
+}
+
+struct Source {
+    kind: ProviderKind,
+    name: String,
+    root: PathBuf,
+    enabled: bool,
+    inventory: PluginInventory,
+    hooks: Vec<HookInfo>,
+}
+
+impl Source {
+    fn provider(&self, file: &str) -> Provider {
+        Provider {
+            kind: self.kind,
+            source: self.name.clone(),
+            file: self.root.join(file).to_string_lossy().to_string(),
+            enabled: self.enabled,
+        }
+    }
+}
+
+// Matchers are tool-name patterns like "Write|Edit"; a missing matcher or "*" matches every tool.
+// Patterns are compared by their alternatives rather than evaluated as regexes.
+fn matchers_overlap(a: Option<&str>, b: Option<&str>) -> bool {
+    let alternatives = |matcher: Option<&str>| -> Option<Vec<String>> {
+        match matcher.map(str::trim) {
+            None | Some("") | Some("*") | Some(".*") => None,
+            Some(matcher) => Some(matcher.split('|').map(|t| t.trim().to_string()).collect()),
+        }
+    };
+    match (alternatives(a), alternatives(b)) {
+        (Some(a), Some(b)) => a.iter().any(|t| b.contains(t)),
+        _ => true,
+    }
+}
+
+fn settings_hooks(path: &Path) -> Vec<HookInfo> {
+    let Ok(Some(settings)) = claude_config::read_json(path) else {
+        return Vec::new();
+    };
+    let file = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    plugin_inspect::parse_hooks(&settings, &file)
+}
+
+fn config_source(kind: ProviderKind, claude_dir: PathBuf, settings: &[PathBuf]) -> Source {
+    let inventory = plugin_inspect::inspect_config_dir(&claude_dir);
+    Source {
+        kind,
+        name: if kind == ProviderKind::Personal { "personal" } else { "project" }.to_string(),
+        root: claude_dir,
+        enabled: true,
+        hooks: settings.iter().flat_map(|path| settings_hooks(path)).collect(),
+        inventory,
+    }
+}
+
+// Installed plugins plus the user's and the project's own commands, agents and hooks, and
+// the installed plugins that couldn't be read
+fn gather(project_path: Option<&str>) -> Result<(Vec<Source>, Vec<UncheckedPlugin>), String> {
+    let states = plugin_settings::list(project_path)?;
+    let mut sources = Vec::new();
+    let mut unchecked = Vec::new();
+
+    let mut seen: Vec<String> = Vec::new();
+    for record in plugins::load_installed()? {
+        let Some(path) = record.install_path.as_deref() else {
+            continue;
+        };
+        if seen.contains(&record.id) {
+            continue;
+        }
+        seen.push(record.id.clone());
+        let root = PathBuf::from(path);
+        let inventory = match plugin_inspect::inspect_path(&root) {
+            Ok(inventory) => inventory,
+            Err(error) => {
+                unchecked.push(UncheckedPlugin { id: record.id, path: path.to_string(), error });
+                continue;
+            }
+        };
+        sources.push(Source {
+            kind: ProviderKind::Plugin,
+            enabled: states.iter().any(|s| s.id == record.id && s.enabled),
+            name: record.id,
+            root,
+            hooks: inventory.hooks.clone(),
+            inventory,
+        });
+    }
+
+    sources.push(config_source(
+        ProviderKind::Personal,
+        claude_config::claude_dir()?,
+        &[plugin_settings::settings_path(SettingsScope::User, None)?],
+    ));
+    if let Some(project) = project_path {
+        sources.push(config_source(
+            ProviderKind::Project,
+            clients::expand_home(project)?.join(".claude"),
+            &[
+                plugin_settings::settings_path(SettingsScope::Project, project_path)?,
+                plugin_settings::settings_path(SettingsScope::Local, project_path)?,
+            ],
+        ));
+    }
+    Ok((sources, unchecked))
+}
+
+fn collisions(sources: &[Source], kind: DefinitionKind) -> Vec<NameCollision> {
+    let mut found: Vec<NameCollision> = Vec::new();
+    for source in sources {
+        let definitions: Vec<(&str, &str)> = match kind {
+            DefinitionKind::Command => source.inventory.commands.iter().map(|c| (c.name.as_str(), c.file.as_str())).collect(),
+            DefinitionKind::Agent => source.inventory.agents.iter().map(|a| (a.name.as_str(), a.file.as_str())).collect(),
+        };
+        for (name, file) in definitions {
+            let provider = source.provider(file);
+            match found.iter_mut().find(|c| c.name == name) {
+                Some(collision) => collision.providers.push(provider),
+                None => found.push(NameCollision { kind, name: name.to_string(), providers: vec![provider] }),
+            }
+        }
+    }
+
+    // A source defining the same name twice is its own problem, not a collision
+    found.retain(|c| {
+        let first = &c.providers[0].source;
+        c.providers.iter().any(|p| &p.source != first)
+    });
+    found
+}
+
+fn hook_overlaps(sources: &[Source]) -> Vec<HookOverlap> {
+    let hooks: Vec<(&Source, &HookInfo)> = sources.iter()
+        .flat_map(|source| source.hooks.iter().map(move |hook| (source, hook)))
+        .collect();
+
+    let mut overlaps: Vec<HookOverlap> = Vec::new();
+    for (index, (source, hook)) in hooks.iter().enumerate() {
+        let overlapping = hooks.iter().enumerate().any(|(other_index, (other_source, other))| {
+            other_index != index
+                && other_source.name != source.name
+                && other.event == hook.event
+                && matchers_overlap(hook.matcher.as_deref(), other.matcher.as_deref())
+        });
+        if !overlapping {
+            continue;
+        }
+
+        let entry = HookEntry {
+            provider: source.provider(&hook.file),
+            matcher: hook.matcher.clone(),
+            command: hook.command.clone(),
+        };
+        match overlaps.iter_mut().find(|o| o.event == hook.event) {
+            Some(overlap) => overlap.entries.push(entry),
+            None => overlaps.push(HookOverlap { event: hook.event.clone(), entries: vec![entry] }),
+        }
+    }
+    overlaps
+}
+
+fn analyze(sources: &[Source], unchecked: Vec<UncheckedPlugin>, candidate: Option<&str>) -> PluginConflictReport {
+    let mut named = collisions(sources, DefinitionKind::Command);
+    named.extend(collisions(sources, DefinitionKind::Agent));
+    let mut overlaps = hook_overlaps(sources);
+
+    if let Some(candidate) = candidate {
+        named.retain(|c| c.providers.iter().any(|p| p.source == candidate));
+        // Only the candidate's hooks and the ones they overlap with, not clashes it isn't part of
+        for overlap in overlaps.iter_mut() {
+            let ours: Vec<Option<String>> = overlap.entries.iter()
+                .filter(|e| e.provider.source == candidate)
+                .map(|e| e.matcher.clone())
+                .collect();
+            overlap.entries.retain(|e| {
+                e.provider.source == candidate
+                    || ours.iter().any(|m| matchers_overlap(m.as_deref(), e.matcher.as_deref()))
+            });
+        }
+        overlaps.retain(|o| o.entries.iter().any(|e| e.provider.source == candidate));
+    }
+    named.sort_by(|a, b| a.name.cmp(&b.name));
+
+    PluginConflictReport {
+        candidate: candidate.map(|c| c.to_string()),
+        collisions: named,
+        hook_overlaps: overlaps,
+        unchecked,
+        error: None,
+    }
+}
+
+pub fn find(project_path: Option<&str>) -> Result<PluginConflictReport, String> {
+    let (sources, unchecked) = gather(project_path)?;
+    Ok(analyze(&sources, unchecked, None))
+}
+
+// Conflicts a plugin would bring in, checked against everything already installed. An
+// installed copy of the same plugin is left out, so upgrades don't conflict with themselves.
+pub fn check_candidate(id: &str, root: &Path, inventory: PluginInventory, project_path: Option<&str>) -> Result<PluginConflictReport, String> {
+    let (sources, mut unchecked) = gather(project_path)?;
+    let mut sources: Vec<Source> = sources.into_iter().filter(|s| s.name != id).collect();
+    unchecked.retain(|p| p.id != id);
+    sources.push(Source {
+        kind: ProviderKind::Plugin,
+        name: id.to_string(),
+        root: root.to_path_buf(),
+        enabled: true,
+        hooks: inventory.hooks.clone(),
+        inventory,
+    });
+    Ok(analyze(&sources, unchecked, Some(id)))
+}
+
+// What installing a plugin from a marketplace would clash with, checked before anything is
+// written. The plugin's files are only fetched, the way inspect_plugin does.
+pub fn check_listed(marketplace: &str, plugin: &str, project_path: Option<&str>) -> Result<PluginConflictReport, String> {
+    let inventory = plugin_inspect::inspect_listed(marketplace, plugin)?;
+    let id = inventory.id.clone().unwrap_or_else(|| plugin.to_string());
+    let (name, marketplace_name) = plugins::split_id(&id);
+    let version = inventory.version.clone().unwrap_or_else(|| "unknown".to_string());
+    // Where an install would put it, so reported files point where they will end up
+    let root = marketplace_name
+        .and_then(|m| plugins::cache_path(&m, &name, &version).ok())
+        .unwrap_or_else(|| PathBuf::from(&id));
+    Ok(check_candidate(&id, &root, inventory, project_path)
+        .unwrap_or_else(|e| PluginConflictReport::unchecked(&id, e)))
+}
+
+#[tauri::command]
+pub async fn check_plugin_conflicts(
+    marketplace: String,
+    plugin: String,
+    project_path: Option<String>,
+) -> Result<PluginConflictReport, String> {
+    tauri::async_runtime::spawn_blocking(move || check_listed(&marketplace, &plugin, project_path.as_deref()))
+        .await
+        .map_err(|e| format!("Plugin conflict check failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn find_plugin_conflicts(project_path: Option<String>) -> Result<PluginConflictReport, String> {
+    tauri::async_runtime::spawn_blocking(move || find(project_path.as_deref()))
+        .await
+        .map_err(|e| format!("Plugin conflict check failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use serde_json::json;
+
+    fn hook(event: &str, matcher: Option<&str>) -> HookInfo {
+        HookInfo {
+            event: event.to_string(),
+            matcher: matcher.map(|m| m.to_string()),
+            hook_type: "command".to_string(),
+            command: Some("./check.sh".to_string()),
+            timeout: None,
+            file: "hooks/hooks.json".to_string(),
+        }
+    }
+
+    fn source(name: &str, commands: &[&str], agents: &[&str], hooks: Vec<HookInfo>) -> Source {
+        let inventory = PluginInventory {
+            id: Some(name.to_string()),
+            name: None,
+            version: None,
+            description: None,
+            author: None,
+            has_manifest: false,
+            git_commit_sha: None,
+            commands: commands.iter().map(|c| plugin_inspect::CommandInfo {
+                name: c.to_string(),
+                file: format!("commands/{}.md", c),
+                description: None,
+                allowed_tools: Vec::new(),
+            }).collect(),
+            agents: agents.iter().map(|a| plugin_inspect::AgentInfo {
+                name: a.to_string(),
+                file: format!("agents/{}.md", a),
+                description: None,
+                tools: Vec::new(),
+                model: None,
+            }).collect(),
+            hooks: hooks.clone(),
+            mcp_servers: Vec::new(),
+            skills: Vec::new(),
+            scripts: Vec::new(),
+            files: Vec::new(),
+            total_size: 0,
+            warnings: Vec::new(),
+        };
+        Source {
+            kind: ProviderKind::Plugin,
+            name: name.to_string(),
+            root: PathBuf::from("/plugins").join(name),
+            enabled: true,
+            inventory,
+            hooks,
+        }
+    }
+
+    fn sources_of(overlap: &HookOverlap) -> Vec<(&str, Option<&str>)> {
+        overlap.entries.iter().map(|e| (e.provider.source.as_str(), e.matcher.as_deref())).collect()
+    }
+
+    #[test]
+    fn matchers_overlap_by_alternatives() {
+        assert!(matchers_overlap(None, Some("Write")));
+        assert!(matchers_overlap(Some("*"), Some("Bash")));
+        assert!(matchers_overlap(Some(".*"), Some("Bash")));
+        assert!(matchers_overlap(Some(""), Some("Bash")));
+        assert!(matchers_overlap(Some("Write|Edit"), Some("Edit")));
+        assert!(matchers_overlap(Some(" Write | Edit "), Some("Edit|MultiEdit")));
+        assert!(!matchers_overlap(Some("Write|Edit"), Some("Bash")));
+        assert!(!matchers_overlap(Some("Write"), Some("WriteFile")));
+    }
+
+    #[test]
+    fn collisions_need_two_sources() {
+        let sources = [
+            source("a@m", &["review", "review", "lint"], &["helper"], Vec::new()),
+            source("b@m", &["review"], &["lint"], Vec::new()),
+        ];
+        let commands = collisions(&sources, DefinitionKind::Command);
+        let agents = collisions(&sources, DefinitionKind::Agent);
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].name, "review");
+        let providers: Vec<&str> = commands[0].providers.iter().map(|p| p.source.as_str()).collect();
+        assert_eq!(providers, ["a@m", "a@m", "b@m"]);
+        assert_eq!(commands[0].providers[2].file, Path::new("/plugins/b@m/commands/review.md").to_string_lossy());
+        // A command and an agent with the same name don't collide
+        assert!(agents.is_empty());
+
+        let alone = [source("a@m", &["review", "review"], &[], Vec::new())];
+        assert!(collisions(&alone, DefinitionKind::Command).is_empty());
+    }
+
+    #[test]
+    fn hook_overlaps_ignore_a_sources_own_hooks() {
+        let sources = [
+            source("a@m", &[], &[], vec![hook("PreToolUse", Some("Write")), hook("PreToolUse", Some("Write|Edit"))]),
+            source("b@m", &[], &[], vec![hook("PreToolUse", Some("Bash")), hook("Stop", None)]),
+        ];
+        assert!(hook_overlaps(&sources).is_empty());
+
+        let sources = [
+            source("a@m", &[], &[], vec![hook("PreToolUse", Some("Write"))]),
+            source("b@m", &[], &[], vec![hook("PreToolUse", None), hook("PostToolUse", None)]),
+        ];
+        let overlaps = hook_overlaps(&sources);
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].event, "PreToolUse");
+        assert_eq!(sources_of(&overlaps[0]), [("a@m", Some("Write")), ("b@m", None)]);
+    }
+
+    #[test]
+    fn analyze_keeps_only_the_candidates_conflicts() {
+        let sources = [
+            source("a@m", &["review", "deploy"], &[], vec![hook("PreToolUse", Some("Bash")), hook("Stop", None)]),
+            source("b@m", &["deploy"], &[], vec![hook("PreToolUse", Some("Write")), hook("Stop", None)]),
+            source("new@m", &["review"], &[], vec![hook("PreToolUse", Some("Write|Edit"))]),
+        ];
+
+        let everything = analyze(&sources, Vec::new(), None);
+        let names: Vec<&str> = everything.collisions.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["deploy", "review"]);
+        assert_eq!(everything.hook_overlaps.len(), 2);
+
+        let report = analyze(&sources, Vec::new(), Some("new@m"));
+        assert_eq!(report.candidate.as_deref(), Some("new@m"));
+        let names: Vec<&str> = report.collisions.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["review"]);
+        // The Stop hooks only clash between a@m and b@m, and a@m's Bash hook with nothing
+        assert_eq!(report.hook_overlaps.len(), 1);
+        assert_eq!(sources_of(&report.hook_overlaps[0]), [("b@m", Some("Write")), ("new@m", Some("Write|Edit"))]);
+        assert_eq!(report.summary(), "command review, hooks on PreToolUse");
+    }
+
+    #[test]
+    fn unreadable_installed_plugins_are_reported_as_unchecked() {
+        let _lock = claude_config::CONFIG_DIR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let root = std::env::temp_dir().join(format!("skiller-test-{}-conflicts-unchecked", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let config = root.join("config");
+        let plugin = root.join("review-plugin");
+        fs::create_dir_all(plugin.join("commands")).unwrap();
+        fs::write(plugin.join("commands/review.md"), "Review the diff").unwrap();
+        fs::create_dir_all(config.join("commands")).unwrap();
+        fs::write(config.join("commands/review.md"), "My own review").unwrap();
+        fs::create_dir_all(config.join("plugins")).unwrap();
+        let installed = json!({"version": 2, "plugins": {
+            "review@m": [{"scope": "user", "installPath": plugin}],
+            "gone@m": [{"scope": "user", "installPath": root.join("gone")}],
+        }});
+        fs::write(config.join("plugins/installed_plugins.json"), installed.to_string()).unwrap();
+        std::env::set_var("CLAUDE_CONFIG_DIR", &config);
+
+        let report = find(None);
+        let candidate = check_candidate("gone@m", &root.join("new"), source("gone@m", &[], &[], Vec::new()).inventory, None);
+        std::env::remove_var("CLAUDE_CONFIG_DIR");
+        let _ = fs::remove_dir_all(&root);
+
+        let report = report.unwrap();
+        assert_eq!(report.collisions.len(), 1);
+        let providers: Vec<&str> = report.collisions[0].providers.iter().map(|p| p.source.as_str()).collect();
+        assert_eq!(providers, ["review@m", "personal"]);
+        assert_eq!(report.unchecked.len(), 1);
+        assert_eq!(report.unchecked[0].id, "gone@m");
+        assert_eq!(report.unchecked[0].path, root.join("gone").to_string_lossy());
+        // Reinstalling the unreadable plugin replaces it, so it isn't listed against itself
+        assert!(candidate.unwrap().unchecked.is_empty());
+    }
+}
@@ -0,0 +1,722 @@
+use std::path::Path;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::archive;
+use crate::clients;
+use crate::installer::{self, SkillFile};
+use crate::marketplace;
+use crate::plugins::{self, FetchedPlugin, PLUGIN_MANIFEST};
+use crate::remote::SKILL_FILE;
+use crate::skill_md;
+
+// Where Claude Code looks when plugin.json doesn't say otherwise
+const DEFAULT_COMMANDS_DIR: &str = "commands";
+const DEFAULT_AGENTS_DIR: &str = "agents";
+const DEFAULT_SKILLS_DIR: &str = "skills";
+const DEFAULT_HOOKS_FILE: &str = "hooks/hooks.json";
+const DEFAULT_MCP_FILE: &str = ".mcp.json";
+
+const SCRIPT_EXTENSIONS: [&str; 7] = [".sh", ".bash", ".zsh", ".py", ".js", ".mjs", ".ps1"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandInfo {
+    // As typed after the plugin prefix, e.g. "review" or "git:commit" for commands/git/commit.md
+    pub name: String,
+    pub file: String,
+    pub description: Option<String>,
+    pub allowed_tools: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentInfo {
+    pub name: String,
+    pub file: String,
+    pub description: Option<String>,
+    pub tools: Vec<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookInfo {
+    pub event: String,
+    pub matcher: Option<String>,
+    pub hook_type: String,
+    // The shell command the hook runs, for "command" hooks
+    pub command: Option<String>,
+    pub timeout: Option<u64>,
+    // File the hook is declared in
+    pub file: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServerInfo {
+    pub name: String,
+    pub transport: String,
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    pub url: Option<String>,
+    // Only the variable names; values can hold secrets
+    pub env: Vec<String>,
+    pub file: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledSkill {
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+    pub allowed_tools: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInventory {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub author: Option<Value>,
+    pub has_manifest: bool,
+    pub git_commit_sha: Option<String>,
+    pub commands: Vec<CommandInfo>,
+    pub agents: Vec<AgentInfo>,
+    pub hooks: Vec<HookInfo>,
+    pub mcp_servers: Vec<McpServerInfo>,
+    pub skills: Vec<BundledSkill>,
+    // Files that can run as programs: shebang scripts and common script extensions
+    pub scripts: Vec<String>,
+    pub files: Vec<String>,
+    pub total_size: u64,
+    pub warnings: Vec<String>,
+}
+
+struct Inspector<'a> {
+    files: &'a [SkillFile],
+    warnings: Vec<String>,
+}
+
+fn relative(file: &SkillFile) -> String {
+    file.path.to_string_lossy().replace('\\', "/")
+}
+
+fn normalize(path: &str) -> String {
+    let path = path.trim().replace('\\', "/");
+    let path = path.trim_start_matches("./").trim_end_matches('/');
+    path.to_string()
+}
+
+// A plugin.json component field: one path, a list of paths, or an inline object
+fn component_paths(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(path)) => vec![normalize(path)],
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).map(normalize).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(|v| v.to_string())
+}
+
+fn tool_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).map(|t| t.to_string()).collect(),
+        Some(Value::String(tools)) => tools.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl<'a> Inspector<'a> {
+    fn file(&self, path: &str) -> Option<&'a SkillFile> {
+        self.files.iter().find(|f| relative(f) == path)
+    }
+
+    // Files at `path`, or beneath it when it is a folder
+    fn under(&self, path: &str) -> Vec<&'a SkillFile> {
+        let prefix = format!("{}/", path);
+        self.files.iter()
+            .filter(|f| {
+                let file = relative(f);
+                path.is_empty() || file == path || file.starts_with(&prefix)
+            })
+            .collect()
+    }
+
+    fn parse_json(&mut self, file: &SkillFile) -> Option<Value> {
+        match serde_json::from_slice(&file.contents) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.warnings.push(format!("{}: invalid JSON: {}", relative(file), e));
+                None
+            }
+        }
+    }
+
+    fn frontmatter(&mut self, file: &SkillFile) -> Option<skill_md::SkillFrontmatter> {
+        let content = String::from_utf8_lossy(&file.contents);
+        if !content.starts_with("---") {
+            return None;
+        }
+        match skill_md::parse_skill_md(&content) {
+            Ok(parsed) => Some(parsed.frontmatter),
+            Err(e) => {
+                self.warnings.push(format!("{}: {}", relative(file), e.message));
+                None
+            }
+        }
+    }
+
+    fn missing(&mut self, field: &str, path: &str) {
+        self.warnings.push(format!("{} lists {} in {}, but it doesn't exist", PLUGIN_MANIFEST, path, field));
+    }
+
+    fn commands(&mut self, manifest: &Value) -> Vec<CommandInfo> {
+        let mut dirs = vec![DEFAULT_COMMANDS_DIR.to_string()];
+        dirs.extend(component_paths(manifest.get("commands")));
+
+        let mut commands: Vec<CommandInfo> = Vec::new();
+        for dir in dirs {
+            let found = self.under(&dir);
+            if found.is_empty() && dir != DEFAULT_COMMANDS_DIR {
+                self.missing("commands", &dir);
+            }
+            for file in found {
+                let path = relative(file);
+                if !path.ends_with(".md") || commands.iter().any(|c| c.file == path) {
+                    continue;
+                }
+                // plugin.json can also list single command files
+                let within = path.strip_prefix(&format!("{}/", dir))
+                    .or_else(|| Path::new(&path).file_name().and_then(|n| n.to_str()))
+                    .unwrap_or(&path);
+                let name = within.trim_end_matches(".md").replace('/', ":");
+                let frontmatter = self.frontmatter(file);
+                commands.push(CommandInfo {
+                    name,
+                    file: path,
+                    description: frontmatter.as_ref().and_then(|f| f.description.clone()),
+                    allowed_tools: frontmatter.map(|f| f.allowed_tools).unwrap_or_default(),
+                });
+            }
+        }
+        commands
+    }
+
+    fn agents(&mut self, manifest: &Value) -> Vec<AgentInfo> {
+        let mut dirs = vec![DEFAULT_AGENTS_DIR.to_string()];
+        dirs.extend(component_paths(manifest.get("agents")));
+
+        let mut agents: Vec<AgentInfo> = Vec::new();
+        for dir in dirs {
+            let found = self.under(&dir);
+            if found.is_empty() && dir != DEFAULT_AGENTS_DIR {
+                self.missing("agents", &dir);
+            }
+            for file in found {
+                let path = relative(file);
+                if !path.ends_with(".md") || agents.iter().any(|a| a.file == path) {
+                    continue;
+                }
+                let stem = Path::new(&path).file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let frontmatter = self.frontmatter(file);
+                agents.push(AgentInfo {
+                    name: frontmatter.as_ref().and_then(|f| f.name.clone()).unwrap_or(stem),
+                    file: path,
+                    description: frontmatter.as_ref().and_then(|f| f.description.clone()),
+                    tools: frontmatter.as_ref().map(|f| tool_list(f.extra.get("tools"))).unwrap_or_default(),
+                    model: frontmatter.and_then(|f| f.extra.get("model").and_then(Value::as_str).map(|m| m.to_string())),
+                });
+            }
+        }
+        agents
+    }
+
+    fn skills(&mut self, manifest: &Value) -> Vec<BundledSkill> {
+        let mut dirs = vec![DEFAULT_SKILLS_DIR.to_string()];
+        dirs.extend(component_paths(manifest.get("skills")));
+
+        let mut skills: Vec<BundledSkill> = Vec::new();
+        for dir in dirs {
+            let found = self.under(&dir);
+            if found.is_empty() && dir != DEFAULT_SKILLS_DIR {
+                self.missing("skills", &dir);
+            }
+            for file in found {
+                let path = relative(file);
+                if file.path.file_name().map(|n| n != SKILL_FILE).unwrap_or(true) {
+                    continue;
+                }
+                let skill_dir = path.trim_end_matches(SKILL_FILE).trim_end_matches('/').to_string();
+                if skills.iter().any(|s| s.path == skill_dir) {
+                    continue;
+                }
+                let folder = Path::new(&skill_dir).file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let frontmatter = self.frontmatter(file);
+                skills.push(BundledSkill {
+                    name: frontmatter.as_ref().and_then(|f| f.name.clone()).unwrap_or(folder),
+                    path: skill_dir,
+                    description: frontmatter.as_ref().and_then(|f| f.description.clone()),
+                    allowed_tools: frontmatter.map(|f| f.allowed_tools).unwrap_or_default(),
+                });
+            }
+        }
+        skills
+    }
+
+    // Config objects declared inline in plugin.json or in the files it (or the default) points at
+    fn configs(&mut self, manifest: &Value, field: &str, default_file: &str) -> Vec<(String, Value)> {
+        let mut configs = Vec::new();
+        if let Some(inline @ Value::Object(_)) = manifest.get(field) {
+            configs.push((PLUGIN_MANIFEST.to_string(), inline.clone()));
+        }
+
+        let mut paths = vec![default_file.to_string()];
+        paths.extend(component_paths(manifest.get(field)));
+        for path in paths {
+            match self.file(&path) {
+                Some(file) => {
+                    if let Some(value) = self.parse_json(file) {
+                        configs.push((path, value));
+                    }
+                }
+                None if path != default_file => self.missing(field, &path),
+                None => {}
+            }
+        }
+        configs
+    }
+
+    fn hooks(&mut self, manifest: &Value) -> Vec<HookInfo> {
+        self.configs(manifest, "hooks", DEFAULT_HOOKS_FILE)
+            .into_iter()
+            .flat_map(|(file, config)| parse_hooks(&config, &file))
+            .collect()
+    }
+
+    fn mcp_servers(&mut self, manifest: &Value) -> Vec<McpServerInfo> {
+        let mut servers = Vec::new();
+        for (file, config) in self.configs(manifest, "mcpServers", DEFAULT_MCP_FILE) {
+            let entries = match config.get("mcpServers") {
+                Some(Value::Object(entries)) => entries.clone(),
+                _ => config.as_object().cloned().unwrap_or_default(),
+            };
+            for (name, server) in entries {
+                if !server.is_object() {
+                    continue;
+                }
+                let url = text(&server, "url");
+                let transport = text(&server, "type")
+                    .unwrap_or_else(|| if url.is_some() { "http" } else { "stdio" }.to_string());
+                servers.push(McpServerInfo {
+                    name,
+                    transport,
+                    command: text(&server, "command"),
+                    args: server.get("args").and_then(Value::as_array)
+                        .map(|args| args.iter().filter_map(Value::as_str).map(|a| a.to_string()).collect())
+                        .unwrap_or_default(),
+                    url,
+                    env: server.get("env").and_then(Value::as_object)
+                        .map(|env| env.keys().cloned().collect())
+                        .unwrap_or_default(),
+                    file: file.clone(),
+                });
+            }
+        }
+        servers
+    }
+}
+
+// Hook entries of a hooks.json, a settings.json or an inline plugin.json object. The first
+// two wrap the events in "hooks"; inline configs may not.
+pub fn parse_hooks(config: &Value, file: &str) -> Vec<HookInfo> {
+    let events = match config.get("hooks") {
+        Some(Value::Object(events)) => events.clone(),
+        Some(_) => return Vec::new(),
+        None => config.as_object().cloned().unwrap_or_default(),
+    };
+
+    let mut hooks = Vec::new();
+    for (event, groups) in events {
+        let Some(groups) = groups.as_array() else {
+            continue;
+        };
+        for group in groups {
+            let matcher = text(group, "matcher");
+            let entries = group.get("hooks").and_then(Value::as_array).cloned().unwrap_or_default();
+            for hook in entries {
+                hooks.push(HookInfo {
+                    event: event.clone(),
+                    matcher: matcher.clone(),
+                    hook_type: text(&hook, "type").unwrap_or_else(|| "command".to_string()),
+                    command: text(&hook, "command"),
+                    timeout: hook.get("timeout").and_then(Value::as_u64),
+                    file: file.to_string(),
+                });
+            }
+        }
+    }
+    hooks
+}
+
+fn is_script(file: &SkillFile) -> bool {
+    let path = relative(file).to_lowercase();
+    file.contents.starts_with(b"#!") || SCRIPT_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+// Builds the inventory from a plugin's files. Fields from a marketplace entry act as the
+// manifest where plugin.json doesn't set them, as they do for non-strict plugins.
+pub fn inventory(files: &[SkillFile], entry_fields: Option<&serde_json::Map<String, Value>>) -> PluginInventory {
+    let mut inspector = Inspector { files, warnings: Vec::new() };
+
+    let mut manifest = serde_json::Map::new();
+    if let Some(fields) = entry_fields {
+        manifest.extend(fields.clone());
+    }
+    let has_manifest = match plugins::read_manifest(files) {
+        Some(Ok(Value::Object(fields))) => {
+            manifest.extend(fields);
+            true
+        }
+        Some(Ok(_)) => {
+            inspector.warnings.push(format!("{} is not a JSON object", PLUGIN_MANIFEST));
+            true
+        }
+        Some(Err(e)) => {
+            inspector.warnings.push(e);
+            true
+        }
+        None => false,
+    };
+    let manifest = Value::Object(manifest);
+
+    let mut paths: Vec<String> = files.iter().map(relative).collect();
+    paths.sort();
+    let mut scripts: Vec<String> = files.iter().filter(|f| is_script(f)).map(relative).collect();
+    scripts.sort();
+
+    PluginInventory {
+        id: None,
+        name: text(&manifest, "name"),
+        version: text(&manifest, "version"),
+        description: text(&manifest, "description"),
+        author: manifest.get("author").cloned(),
+        has_manifest,
+        git_commit_sha: None,
+        commands: inspector.commands(&manifest),
+        agents: inspector.agents(&manifest),
+        hooks: inspector.hooks(&manifest),
+        mcp_servers: inspector.mcp_servers(&manifest),
+        skills: inspector.skills(&manifest),
+        scripts,
+        files: paths,
+        total_size: files.iter().map(|f| f.contents.len() as u64).sum(),
+        warnings: inspector.warnings,
+    }
+}
+
+// A plugin folder or archive on disk, e.g. an installed copy under plugins/cache
+pub fn inspect_path(path: &Path) -> Result<PluginInventory, String> {
+    let files = archive::read_source_with(path, true)?;
+    let files = if path.is_dir() { files } else { plugins::strip_wrapper(files) };
+    Ok(inventory(&files, None))
+}
+
+// Commands and agents a .claude folder defines for a user or project, read the way a
+// plugin's are
+pub fn inspect_config_dir(claude_dir: &Path) -> PluginInventory {
+    let mut files = Vec::new();
+    for dir in [DEFAULT_COMMANDS_DIR, DEFAULT_AGENTS_DIR] {
+        if let Ok(found) = archive::read_source_with(&claude_dir.join(dir), false) {
+            files.extend(found.into_iter().map(|f| SkillFile { path: Path::new(dir).join(f.path), contents: f.contents, mode: f.mode }));
+        }
+    }
+    inventory(&files, None)
+}
+
+// A plugin listed in a marketplace, fetched without installing it. A marketplace that has no
+// copy on disk is only downloaded to a temp folder for the inspection and removed afterwards.
+pub fn inspect_listed(marketplace_source: &str, plugin: &str) -> Result<PluginInventory, String> {
+    let (loaded, temporary) = marketplace::resolve_detached(marketplace_source)?;
+    let result = loaded.manifest.plugin(plugin).and_then(|entry| {
+        let FetchedPlugin { files, git_commit_sha } = plugins::fetch_plugin(&loaded, entry)?;
+        let mut fields = entry.extra.clone();
+        fields.insert("name".to_string(), Value::from(entry.name.clone()));
+        if let Some(description) = &entry.description {
+            fields.insert("description".to_string(), Value::from(description.clone()));
+        }
+        if let Some(version) = &entry.version {
+            fields.insert("version".to_string(), Value::from(version.clone()));
+        }
+
+        let mut inventory = inventory(&files, Some(&fields));
+        inventory.id = Some(plugins::plugin_id(&entry.name, &loaded.manifest.name));
+        inventory.git_commit_sha = git_commit_sha;
+        if !inventory.has_manifest && entry.strict.unwrap_or(true) {
+            inventory.warnings.push(format!(
+                "Plugin has no {} and the marketplace marks it strict, so it can't be installed",
+                PLUGIN_MANIFEST
+            ));
+        }
+        Ok(inventory)
+    });
+
+    if let Some(temporary) = temporary {
+        let _ = installer::remove_path(&temporary);
+    }
+    result
+}
+
+// Either `path` to a plugin on disk, or `marketplace` (a known name or a source) and `plugin`
+#[tauri::command]
+pub async fn inspect_plugin(
+    path: Option<String>,
+    marketplace: Option<String>,
+    plugin: Option<String>,
+) -> Result<PluginInventory, String> {
+    tauri::async_runtime::spawn_blocking(move || match (path, marketplace, plugin) {
+        (Some(path), _, _) => inspect_path(&clients::expand_home(&path)?),
+        (None, Some(marketplace), Some(plugin)) => inspect_listed(&marketplace, &plugin),
+        _ => Err("Pass a plugin path, or a marketplace and plugin name".to_string()),
+    })
+    .await
+    .map_err(|e| format!("Plugin inspection failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use serde_json::json;
+
+    fn file(path: &str, contents: &str) -> SkillFile {
+        SkillFile { path: PathBuf::from(path), contents: contents.into(), mode: None }
+    }
+
+    fn manifest(value: Value) -> SkillFile {
+        file(PLUGIN_MANIFEST, &value.to_string())
+    }
+
+    fn names<T>(items: &[T], name: impl Fn(&T) -> (&str, &str)) -> Vec<(String, String)> {
+        items.iter()
+            .map(|item| {
+                let (a, b) = name(item);
+                (a.to_string(), b.to_string())
+            })
+            .collect()
+    }
+
+    fn inventory_of_manifest(contents: &str) -> PluginInventory {
+        inventory(&[file(PLUGIN_MANIFEST, contents)], None)
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+    }
+
+    const HOOKS: &str = r#"{"hooks": {"PreToolUse": [{"matcher": "Write|Edit", "hooks": [{"type": "command", "command": "./lint.sh", "timeout": 30}]}]}}"#;
+
+    #[test]
+    fn default_folders_are_read_without_a_manifest() {
+        let files = [
+            file("commands/review.md", "---\ndescription: Review the diff\nallowed-tools: Read, Grep\n---\nReview it"),
+            file("commands/git/commit.md", "Commit the changes"),
+            file("commands/notes.txt", "not a command"),
+            file("agents/helper.md", "---\nname: fixer\ndescription: Fixes things\ntools: Read, Edit\nmodel: sonnet\n---\n"),
+            file("agents/plain.md", "No frontmatter"),
+            file("skills/pdf/SKILL.md", "---\nname: pdf\ndescription: Fill forms\n---\n"),
+            file("hooks/hooks.json", HOOKS),
+            file(".mcp.json", r#"{"mcpServers": {"db": {"command": "db-server", "args": ["--port", "5432"], "env": {"DB_TOKEN": "secret"}}}}"#),
+            file("hooks/lint.sh", "#!/bin/sh\nexit 0\n"),
+        ];
+        let inventory = inventory(&files, None);
+
+        assert!(!inventory.has_manifest);
+        assert!(inventory.warnings.is_empty(), "{:?}", inventory.warnings);
+        assert_eq!(names(&inventory.commands, |c| (&c.name, &c.file)), pairs(&[
+            ("review", "commands/review.md"),
+            ("git:commit", "commands/git/commit.md"),
+        ]));
+        assert_eq!(inventory.commands[0].description.as_deref(), Some("Review the diff"));
+        assert_eq!(inventory.commands[0].allowed_tools, ["Read", "Grep"]);
+        assert_eq!(names(&inventory.agents, |a| (&a.name, &a.file)), pairs(&[
+            ("fixer", "agents/helper.md"),
+            ("plain", "agents/plain.md"),
+        ]));
+        assert_eq!(inventory.agents[0].tools, ["Read", "Edit"]);
+        assert_eq!(inventory.agents[0].model.as_deref(), Some("sonnet"));
+        assert_eq!(names(&inventory.skills, |s| (&s.name, &s.path)), pairs(&[("pdf", "skills/pdf")]));
+        assert_eq!(inventory.hooks.len(), 1);
+        assert_eq!(inventory.hooks[0].file, "hooks/hooks.json");
+        assert_eq!(inventory.mcp_servers.len(), 1);
+        let server = &inventory.mcp_servers[0];
+        assert_eq!((server.name.as_str(), server.transport.as_str()), ("db", "stdio"));
+        assert_eq!(server.args, ["--port", "5432"]);
+        assert_eq!(server.env, ["DB_TOKEN"]);
+        assert_eq!(inventory.scripts, ["hooks/lint.sh"]);
+        assert_eq!(inventory.files.len(), files.len());
+    }
+
+    #[test]
+    fn manifest_paths_add_to_the_default_folders() {
+        let files = [
+            manifest(json!({
+                "name": "tools",
+                "version": "1.2.0",
+                "commands": ["./extra/cmds/", "./single/one.md"],
+                "agents": "custom-agents",
+                "skills": ["bundled"],
+                "hooks": "./config/hooks.json",
+                "mcpServers": "config/mcp.json",
+            })),
+            file("commands/review.md", "Review"),
+            file("extra/cmds/deploy.md", "Deploy"),
+            file("extra/cmds/db/migrate.md", "Migrate"),
+            file("single/one.md", "One"),
+            file("custom-agents/planner.md", "Plan"),
+            file("bundled/docx/SKILL.md", "---\ndescription: Word files\n---\n"),
+            file("config/hooks.json", HOOKS),
+            file("config/mcp.json", r#"{"search": {"url": "https://example.com/mcp"}}"#),
+        ];
+        let inventory = inventory(&files, None);
+
+        assert!(inventory.has_manifest);
+        assert!(inventory.warnings.is_empty(), "{:?}", inventory.warnings);
+        assert_eq!(inventory.name.as_deref(), Some("tools"));
+        assert_eq!(inventory.version.as_deref(), Some("1.2.0"));
+        assert_eq!(names(&inventory.commands, |c| (&c.name, &c.file)), pairs(&[
+            ("review", "commands/review.md"),
+            ("deploy", "extra/cmds/deploy.md"),
+            ("db:migrate", "extra/cmds/db/migrate.md"),
+            ("one", "single/one.md"),
+        ]));
+        assert_eq!(names(&inventory.agents, |a| (&a.name, &a.file)), pairs(&[("planner", "custom-agents/planner.md")]));
+        // Without a name in its frontmatter a skill is named after its folder
+        assert_eq!(names(&inventory.skills, |s| (&s.name, &s.path)), pairs(&[("docx", "bundled/docx")]));
+        assert_eq!(inventory.hooks[0].file, "config/hooks.json");
+        let server = &inventory.mcp_servers[0];
+        assert_eq!((server.name.as_str(), server.transport.as_str(), server.file.as_str()), ("search", "http", "config/mcp.json"));
+    }
+
+    #[test]
+    fn inline_and_file_configs_are_both_read() {
+        let files = [
+            manifest(json!({
+                "name": "tools",
+                "hooks": {"Stop": [{"hooks": [{"command": "./done.sh"}]}]},
+                "mcpServers": {"inline": {"type": "sse", "url": "https://example.com/sse"}},
+            })),
+            file("hooks/hooks.json", HOOKS),
+            file(".mcp.json", r#"{"mcpServers": {"db": {"command": "db-server"}, "bad": "not an object"}}"#),
+        ];
+        let inventory = inventory(&files, None);
+
+        assert!(inventory.warnings.is_empty(), "{:?}", inventory.warnings);
+        let hooks: Vec<(&str, &str)> = inventory.hooks.iter().map(|h| (h.event.as_str(), h.file.as_str())).collect();
+        assert_eq!(hooks, [("Stop", PLUGIN_MANIFEST), ("PreToolUse", "hooks/hooks.json")]);
+        assert_eq!(inventory.hooks[0].hook_type, "command");
+        let servers: Vec<(&str, &str, &str)> = inventory.mcp_servers.iter()
+            .map(|s| (s.name.as_str(), s.transport.as_str(), s.file.as_str()))
+            .collect();
+        assert_eq!(servers, [("inline", "sse", PLUGIN_MANIFEST), ("db", "stdio", ".mcp.json")]);
+    }
+
+    #[test]
+    fn missing_paths_and_broken_files_are_warned_about() {
+        let files = [
+            manifest(json!({"commands": "./nope", "agents": ["gone"], "hooks": "hooks/extra.json", "mcpServers": "missing.json"})),
+            file("hooks/hooks.json", "{ not json"),
+            file("agents/broken.md", "---\nname: [broken\n---\n"),
+        ];
+        let inventory = inventory(&files, None);
+
+        assert_eq!(inventory.warnings.len(), 6, "{:?}", inventory.warnings);
+        for expected in [
+            format!("{} lists nope in commands, but it doesn't exist", PLUGIN_MANIFEST),
+            format!("{} lists gone in agents, but it doesn't exist", PLUGIN_MANIFEST),
+            format!("{} lists hooks/extra.json in hooks, but it doesn't exist", PLUGIN_MANIFEST),
+            format!("{} lists missing.json in mcpServers, but it doesn't exist", PLUGIN_MANIFEST),
+        ] {
+            assert!(inventory.warnings.contains(&expected), "{:?}", inventory.warnings);
+        }
+        assert!(inventory.warnings.iter().any(|w| w.starts_with("hooks/hooks.json: invalid JSON")));
+        assert!(inventory.warnings.iter().any(|w| w.starts_with("agents/broken.md: ")));
+        // The agent is still listed, named after its file
+        assert_eq!(inventory.agents[0].name, "broken");
+
+        let broken = inventory_of_manifest("{ not json");
+        assert!(broken.has_manifest);
+        assert!(broken.warnings[0].starts_with(&format!("Invalid {}", PLUGIN_MANIFEST)));
+        let not_object = inventory_of_manifest("[]");
+        assert_eq!(not_object.warnings, [format!("{} is not a JSON object", PLUGIN_MANIFEST)]);
+    }
+
+    #[test]
+    fn marketplace_fields_stand_in_for_a_missing_manifest() {
+        let fields = json!({"name": "listed", "version": "0.1.0", "commands": "extra"});
+        let files = [file("extra/run.md", "Run")];
+        let inventory = inventory(&files, fields.as_object());
+
+        assert!(!inventory.has_manifest);
+        assert_eq!(inventory.name.as_deref(), Some("listed"));
+        assert_eq!(inventory.version.as_deref(), Some("0.1.0"));
+        assert_eq!(inventory.commands[0].name, "run");
+    }
+
+    #[test]
+    fn parse_hooks_reads_wrapped_and_bare_event_maps() {
+        let settings = json!({"model": "opus", "hooks": {
+            "PreToolUse": [
+                {"matcher": "Bash", "hooks": [{"type": "command", "command": "./guard.sh", "timeout": 5}, {"type": "prompt"}]},
+            ],
+            "Stop": "not a list",
+        }});
+        let hooks = parse_hooks(&settings, "settings.json");
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].event, "PreToolUse");
+        assert_eq!(hooks[0].matcher.as_deref(), Some("Bash"));
+        assert_eq!(hooks[0].command.as_deref(), Some("./guard.sh"));
+        assert_eq!(hooks[0].timeout, Some(5));
+        assert_eq!(hooks[1].hook_type, "prompt");
+        assert_eq!(hooks[1].command, None);
+        assert!(hooks.iter().all(|h| h.file == "settings.json"));
+
+        let bare = parse_hooks(&json!({"SessionStart": [{"hooks": [{"command": "./hello.sh"}]}]}), PLUGIN_MANIFEST);
+        assert_eq!(bare.len(), 1);
+        assert_eq!(bare[0].matcher, None);
+        assert!(parse_hooks(&json!({"hooks": ["not", "an", "object"]}), "settings.json").is_empty());
+        assert!(parse_hooks(&json!("nothing"), "settings.json").is_empty());
+    }
+
+    #[test]
+    fn config_dirs_are_read_like_plugins() {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-inspect-config", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("commands/team")).unwrap();
+        std::fs::create_dir_all(dir.join("agents")).unwrap();
+        std::fs::write(dir.join("commands/team/standup.md"), "Standup").unwrap();
+        std::fs::write(dir.join("agents/reviewer.md"), "Review").unwrap();
+        std::fs::write(dir.join("settings.json"), HOOKS).unwrap();
+
+        let inventory = inspect_config_dir(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(names(&inventory.commands, |c| (&c.name, &c.file)), pairs(&[("team:standup", "commands/team/standup.md")]));
+        assert_eq!(names(&inventory.agents, |a| (&a.name, &a.file)), pairs(&[("reviewer", "agents/reviewer.md")]));
+        // Settings hooks are read separately by the conflict check
+        assert!(inventory.hooks.is_empty());
+        assert_eq!(inventory.files, ["agents/reviewer.md", "commands/team/standup.md"]);
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::claude_config::{self, TouchedFile};
+use crate::clients;
+use crate::plugins;
+
+// Claude Code reads enabledPlugins from all three; later scopes override earlier ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingsScope {
+    User,
+    Project,
+    Local,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeSetting {
+    pub scope: SettingsScope,
+    pub path: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSetting {
+    pub id: String,
+    pub name: String,
+    pub marketplace: Option<String>,
+    pub installed: bool,
+    pub version: Option<String>,
+    // What Claude Code will do: the value from the most specific scope that mentions the plugin
+    pub enabled: bool,
+    pub decided_by: Option<SettingsScope>,
+    pub scopes: Vec<ScopeSetting>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginToggleResult {
+    pub touched: TouchedFile,
+    // Effective state afterwards; a more specific scope can still override the change
+    pub plugin: PluginSetting,
+}
+
+impl SettingsScope {
+    // The value installed_plugins.json uses in a record's "scope"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingsScope::User => "user",
+            SettingsScope::Project => "project",
+            SettingsScope::Local => "local",
+        }
+    }
+}
+
+const SCOPES: [SettingsScope; 3] = [SettingsScope::User, SettingsScope::Project, SettingsScope::Local];
+
+pub fn settings_path(scope: SettingsScope, project_path: Option<&str>) -> Result<PathBuf, String> {
+    if scope == SettingsScope::User {
+        return claude_config::user_settings_path();
+    }
+
+    let project = project_path
+        .ok_or_else(|| format!("{} settings need a project path", scope_label(scope)))?;
+    let dir = clients::expand_home(project)?.join(".claude");
+    Ok(match scope {
+        SettingsScope::Local => dir.join("settings.local.json"),
+        _ => dir.join("settings.json"),
+    })
+}
+
+fn scope_label(scope: SettingsScope) -> &'static str {
+    match scope {
+        SettingsScope::User => "User",
+        SettingsScope::Project => "Project",
+        SettingsScope::Local => "Local",
+    }
+}
+
+// enabledPlugins of one settings file; missing files and non-boolean values count as unset
+fn read_enabled(path: &Path) -> Result<BTreeMap<String, bool>, String> {
+    let Some(settings) = claude_config::read_json(path)? else {
+        return Ok(BTreeMap::new());
+    };
+    Ok(settings.get("enabledPlugins")
+        .and_then(Value::as_object)
+        .map(|plugins| {
+            plugins.iter()
+                .filter_map(|(id, value)| value.as_bool().map(|enabled| (id.clone(), enabled)))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+// Every plugin that is installed or mentioned in a settings file, with its state per scope
+pub fn list(project_path: Option<&str>) -> Result<Vec<PluginSetting>, String> {
+    let mut plugins: BTreeMap<String, PluginSetting> = BTreeMap::new();
+    let entry = |plugins: &mut BTreeMap<String, PluginSetting>, id: &str| {
+        plugins.entry(id.to_string()).or_insert_with(|| {
+            let (name, marketplace) = plugins::split_id(id);
+            PluginSetting {
+                id: id.to_string(),
+                name,
+                marketplace,
+                installed: false,
+                version: None,
+                enabled: false,
+                decided_by: None,
+                scopes: Vec::new(),
+            }
+        });
+    };
+
+    for record in plugins::load_installed()? {
+        entry(&mut plugins, &record.id);
+        if let Some(plugin) = plugins.get_mut(&record.id) {
+            plugin.installed = true;
+            if plugin.version.is_none() || record.scope == "user" {
+                plugin.version = record.version;
+            }
+        }
+    }
+
+    for scope in SCOPES {
+        if scope != SettingsScope::User && project_path.is_none() {
+            continue;
+        }
+        let path = settings_path(scope, project_path)?;
+        let path_string = clients::path_to_string(&path)?;
+        for (id, enabled) in read_enabled(&path)? {
+            entry(&mut plugins, &id);
+            if let Some(plugin) = plugins.get_mut(&id) {
+                plugin.scopes.push(ScopeSetting { scope, path: path_string.clone(), enabled });
+                plugin.enabled = enabled;
+                plugin.decided_by = Some(scope);
+            }
+        }
+    }
+
+    Ok(plugins.into_values().collect())
+}
+
+pub fn set_enabled(id: &str, enabled: bool, scope: SettingsScope, project_path: Option<&str>) -> Result<PluginToggleResult, String> {
+    if plugins::split_id(id).1.is_none() {
+        return Err(format!("Plugin id must look like name@marketplace: {}", id));
+    }
+
+    let path = settings_path(scope, project_path)?;
+    let touched = claude_config::set_plugin_enabled(&path, id, enabled)?;
+    let plugin = list(project_path)?
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Plugin {} not found after update", id))?;
+
+    Ok(PluginToggleResult { touched, plugin })
+}
+
+#[tauri::command]
+pub async fn list_plugin_settings(project_path: Option<String>) -> Result<Vec<PluginSetting>, String> {
+    tauri::async_runtime::spawn_blocking(move || list(project_path.as_deref()))
+        .await
+        .map_err(|e| format!("Reading plugin settings failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn set_plugin_enabled(
+    plugin_id: String,
+    enabled: bool,
+    scope: Option<SettingsScope>,
+    project_path: Option<String>,
+) -> Result<PluginToggleResult, String> {
+    let scope = scope.unwrap_or(SettingsScope::User);
+    tauri::async_runtime::spawn_blocking(move || set_enabled(&plugin_id, enabled, scope, project_path.as_deref()))
+        .await
+        .map_err(|e| format!("Updating plugin settings failed: {}", e))?
+}
@@ -0,0 +1,940 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::archive;
+use crate::claude_config::{self, FileAction, TouchedFile};
+use crate::clients;
+use crate::installed;
+use crate::installer::{self, SkillFile};
+use crate::marketplace::{self, LoadedMarketplace, MarketplaceSource, PluginEntry, PluginSource, RemotePluginSource};
+use crate::plugin_conflicts::{self, PluginConflictReport};
+use crate::plugin_inspect::{self, McpServerInfo};
+use crate::plugin_settings::{self, SettingsScope};
+use crate::remote;
+
+pub const PLUGIN_MANIFEST: &str = ".claude-plugin/plugin.json";
+const INSTALLED_PLUGINS_FILE: &str = "installed_plugins.json";
+const CACHE_DIR: &str = "cache";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInstallReport {
+    // "name@marketplace", the key Claude Code uses in settings and installed_plugins.json
+    pub id: String,
+    pub name: String,
+    pub marketplace: String,
+    pub version: String,
+    pub install_path: String,
+    pub git_commit_sha: Option<String>,
+    // Files written under install_path, relative to it
+    pub files: Vec<String>,
+    pub touched: Vec<TouchedFile>,
+    // Commands, agents and hooks that clash with what is already installed; a warning unless
+    // the install was asked to stop on conflicts
+    pub conflicts: PluginConflictReport,
+}
+
+// One record from installed_plugins.json
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledPlugin {
+    pub id: String,
+    pub name: String,
+    pub marketplace: Option<String>,
+    pub scope: String,
+    pub version: Option<String>,
+    pub install_path: Option<String>,
+    pub project_path: Option<String>,
+}
+
+// A plugin's files, read from its source but not installed anywhere yet
+pub struct FetchedPlugin {
+    pub files: Vec<SkillFile>,
+    pub git_commit_sha: Option<String>,
+}
+
+pub fn plugin_id(name: &str, marketplace: &str) -> String {
+    format!("{}@{}", name, marketplace)
+}
+
+// "name@marketplace" split into its parts; ids without a marketplace are kept whole
+pub fn split_id(id: &str) -> (String, Option<String>) {
+    match id.rsplit_once('@') {
+        Some((name, marketplace)) if !name.is_empty() && !marketplace.is_empty() => {
+            (name.to_string(), Some(marketplace.to_string()))
+        }
+        _ => (id.to_string(), None),
+    }
+}
+
+pub fn installed_plugins_path() -> Result<PathBuf, String> {
+    Ok(claude_config::plugins_dir()?.join(INSTALLED_PLUGINS_FILE))
+}
+
+pub fn cache_path(marketplace: &str, name: &str, version: &str) -> Result<PathBuf, String> {
+    for part in [marketplace, name, version] {
+        clients::validate_skill_name(part)
+            .map_err(|_| format!("Invalid plugin path component: {}", part))?;
+    }
+    Ok(claude_config::plugins_dir()?.join(CACHE_DIR).join(marketplace).join(name).join(version))
+}
+
+// Relative sources live inside the marketplace; metadata.pluginRoot is prepended to
+// sources that don't start with "./"
+fn resolve_relative(loaded: &LoadedMarketplace, source: &str) -> Result<PathBuf, String> {
+    let plugin_root = loaded.manifest.metadata.as_ref().and_then(|m| m.plugin_root.as_deref());
+    let relative = match plugin_root {
+        Some(root) if !source.starts_with("./") => Path::new(root).join(source),
+        _ => PathBuf::from(source),
+    };
+
+    let escapes = relative.components()
+        .any(|c| matches!(c, Component::ParentDir | Component::RootDir | Component::Prefix(_)));
+    if escapes {
+        return Err(format!("Plugin source {} points outside the marketplace", source));
+    }
+
+    let dir = loaded.root.join(relative);
+    if !dir.is_dir() {
+        return Err(format!("Plugin source {} not found in marketplace {}", source, loaded.manifest.name));
+    }
+    Ok(dir)
+}
+
+fn is_archive_url(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    path.ends_with(".zip") || path.ends_with(".tar.gz") || path.ends_with(".tgz")
+}
+
+// Archives usually wrap the plugin in one folder; the files are re-rooted beneath it
+pub fn strip_wrapper(files: Vec<SkillFile>) -> Vec<SkillFile> {
+    let first = |f: &SkillFile| f.path.components().next().map(|c| c.as_os_str().to_os_string());
+    let Some(wrapper) = files.first().and_then(first) else {
+        return files;
+    };
+    let wrapped = files.iter().all(|f| f.path.components().count() > 1 && first(f).as_ref() == Some(&wrapper));
+    if !wrapped {
+        return files;
+    }
+
+    files.into_iter()
+        .map(|f| SkillFile {
+            path: f.path.strip_prefix(&wrapper).map(Path::to_path_buf).unwrap_or(f.path),
+            contents: f.contents,
+            mode: f.mode,
+        })
+        .collect()
+}
+
+fn clone_plugin(url: &str, git_ref: Option<&str>) -> Result<FetchedPlugin, String> {
+    let staging = installer::sibling_temp_path(&claude_config::plugins_dir()?.join(CACHE_DIR).join("plugin"), "clone");
+    installer::ensure_parent_dir(&staging)?;
+
+    let fetched = marketplace::clone_repo(url, git_ref, &staging).and_then(|_| {
+        Ok(FetchedPlugin {
+            files: archive::read_source_with(&staging, true)?,
+            git_commit_sha: marketplace::head_commit(&staging),
+        })
+    });
+    let _ = installer::remove_path(&staging);
+    fetched
+}
+
+fn download_plugin(url: &str) -> Result<FetchedPlugin, String> {
+    let bytes = remote::fetch_bytes(url)?;
+    let extension = if url.to_lowercase().contains(".zip") { "zip" } else { "tar.gz" };
+    let download = std::env::temp_dir().join(format!(
+        "skiller-plugin-{}-{}.{}",
+        std::process::id(),
+        installed::timestamp_now().replace(':', "-"),
+        extension
+    ));
+    fs::write(&download, bytes)
+        .map_err(|e| format!("Failed to save download: {}", e))?;
+
+    let files = archive::read_source_with(&download, true);
+    let _ = fs::remove_file(&download);
+    Ok(FetchedPlugin { files: strip_wrapper(files?), git_commit_sha: None })
+}
+
+pub fn fetch_plugin(loaded: &LoadedMarketplace, entry: &PluginEntry) -> Result<FetchedPlugin, String> {
+    match &entry.source {
+        PluginSource::Path(source) => {
+            if matches!(loaded.source, MarketplaceSource::Url { .. }) {
+                return Err(format!(
+                    "Plugin {} uses a relative source, which a marketplace served from a URL can't provide",
+                    entry.name
+                ));
+            }
+            let dir = resolve_relative(loaded, source)?;
+            Ok(FetchedPlugin {
+                files: archive::read_source_with(&dir, true)?,
+                git_commit_sha: marketplace::head_commit(&loaded.root),
+            })
+        }
+        PluginSource::Remote(RemotePluginSource::Github { repo, git_ref }) => {
+            clone_plugin(&marketplace::github_url(repo), git_ref.as_deref())
+        }
+        PluginSource::Remote(RemotePluginSource::Git { url, git_ref }) => clone_plugin(url, git_ref.as_deref()),
+        PluginSource::Remote(RemotePluginSource::Url { url, git_ref }) => {
+            if is_archive_url(url) {
+                download_plugin(url)
+            } else {
+                clone_plugin(url, git_ref.as_deref())
+            }
+        }
+    }
+}
+
+pub fn read_manifest(files: &[SkillFile]) -> Option<Result<Value, String>> {
+    let manifest = files.iter().find(|f| f.path == Path::new(PLUGIN_MANIFEST))?;
+    Some(serde_json::from_slice(&manifest.contents)
+        .map_err(|e| format!("Invalid {}: {}", PLUGIN_MANIFEST, e)))
+}
+
+fn manifest_version(files: &[SkillFile]) -> Option<String> {
+    let value = read_manifest(files)?.ok()?;
+    value.get("version")?.as_str().map(|v| v.to_string())
+}
+
+// Every record in installed_plugins.json, in either of its formats
+pub fn load_installed() -> Result<Vec<InstalledPlugin>, String> {
+    let Some(root) = claude_config::read_json(&installed_plugins_path()?)? else {
+        return Ok(Vec::new());
+    };
+    let Some(plugins) = root.get("plugins").and_then(Value::as_object) else {
+        return Ok(Vec::new());
+    };
+
+    let text = |record: &serde_json::Map<String, Value>, key: &str| {
+        record.get(key).and_then(Value::as_str).map(|v| v.to_string())
+    };
+    let mut installed = Vec::new();
+    for (id, value) in plugins {
+        let records: Vec<&serde_json::Map<String, Value>> = match value {
+            Value::Array(records) => records.iter().filter_map(Value::as_object).collect(),
+            Value::Object(record) => vec![record],
+            _ => continue,
+        };
+        let (name, marketplace) = split_id(id);
+        for record in records {
+            installed.push(InstalledPlugin {
+                id: id.clone(),
+                name: name.clone(),
+                marketplace: marketplace.clone(),
+                scope: text(record, "scope").unwrap_or_else(|| "user".to_string()),
+                version: text(record, "version"),
+                install_path: text(record, "installPath"),
+                project_path: text(record, "projectPath"),
+            });
+        }
+    }
+    Ok(installed)
+}
+
+fn record_install(id: &str, record: serde_json::Map<String, Value>) -> Result<TouchedFile, String> {
+    let id = id.to_string();
+    let key = format!("plugins.{}", id);
+
+    claude_config::update_json(&installed_plugins_path()?, vec![key], move |root| add_record(root, id, record))
+}
+
+// Puts a user-scope record into installed_plugins.json in whichever format the file uses
+fn add_record(root: &mut serde_json::Map<String, Value>, id: String, record: serde_json::Map<String, Value>) -> Result<(), String> {
+    if root.is_empty() {
+        root.insert("version".to_string(), Value::from(2));
+    }
+    let format = root.get("version").and_then(Value::as_u64).unwrap_or(1);
+    let plugins = root.entry("plugins")
+        .or_insert_with(|| Value::Object(Default::default()))
+        .as_object_mut()
+        .ok_or_else(|| format!("{} has no plugins object", INSTALLED_PLUGINS_FILE))?;
+
+    // Version 1 keeps one record per plugin, version 2 a list with one record per scope
+    let existing = match plugins.get_mut(&id) {
+        Some(Value::Array(records)) => records.iter_mut()
+            .filter_map(Value::as_object_mut)
+            .find(|r| r.get("scope").and_then(Value::as_str).unwrap_or("user") == "user"),
+        Some(Value::Object(record)) => Some(record),
+        _ => None,
+    };
+
+    match existing {
+        Some(existing) => {
+            let unchanged = record.iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "installedAt" | "lastUpdated"))
+                .all(|(key, value)| existing.get(key) == Some(value));
+            if !unchanged {
+                existing.extend(record.into_iter().filter(|(key, _)| key != "installedAt"));
+            }
+        }
+        None if format >= 2 => match plugins.entry(id).or_insert_with(|| Value::Array(Vec::new())) {
+            Value::Array(records) => records.push(Value::Object(record)),
+            _ => return Err(format!("{} has an unexpected plugin record", INSTALLED_PLUGINS_FILE)),
+        },
+        None => {
+            let mut record = record;
+            record.remove("scope");
+            plugins.insert(id, Value::Object(record));
+        }
+    }
+    Ok(())
+}
+
+// Materializes a plugin from a marketplace into ~/.claude/plugins/cache and registers it
+// the way `/plugin install` does. `marketplace` is a known marketplace name or a source.
+// With `stop_on_conflict`, nothing is installed when the plugin clashes with what is there.
+pub fn install(marketplace: &str, plugin: &str, enable: bool, stop_on_conflict: bool) -> Result<PluginInstallReport, String> {
+    let (loaded, fetched) = marketplace::resolve(marketplace)?;
+    let entry = loaded.manifest.plugin(plugin)?;
+    let FetchedPlugin { files, git_commit_sha } = fetch_plugin(&loaded, entry)?;
+    if files.is_empty() {
+        return Err(format!("Plugin {} has no files", entry.name));
+    }
+    if entry.strict.unwrap_or(true) && !files.iter().any(|f| f.path == Path::new(PLUGIN_MANIFEST)) {
+        return Err(format!("Plugin {} has no {} and the marketplace marks it strict", entry.name, PLUGIN_MANIFEST));
+    }
+
+    let version = entry.version.clone()
+        .or_else(|| manifest_version(&files))
+        .or_else(|| git_commit_sha.as_ref().map(|sha| sha.chars().take(12).collect()))
+        .unwrap_or_else(|| "unknown".to_string());
+    let marketplace_name = loaded.manifest.name.clone();
+    let id = plugin_id(&entry.name, &marketplace_name);
+
+    let target = cache_path(&marketplace_name, &entry.name, &version)?;
+    let inventory = plugin_inspect::inventory(&files, Some(&entry.extra));
+    // The check is advice; if it can't run, the install still goes ahead
+    let conflicts = plugin_conflicts::check_candidate(&id, &target, inventory, None)
+        .unwrap_or_else(|e| PluginConflictReport::unchecked(&id, e));
+    if stop_on_conflict && conflicts.has_conflicts() {
+        return Err(format!("Plugin {} conflicts with what is already installed: {}", id, conflicts.summary()));
+    }
+
+    let mut touched = Vec::new();
+    if fetched {
+        touched.push(marketplace::register(&loaded, false)?);
+    }
+    installer::install_files(&target, &files)?;
+    let install_path = clients::path_to_string(&target)?;
+
+    let now = installed::timestamp_now();
+    let mut record = serde_json::Map::new();
+    record.insert("scope".to_string(), Value::from("user"));
+    record.insert("installPath".to_string(), Value::from(install_path.clone()));
+    record.insert("version".to_string(), Value::from(version.clone()));
+    record.insert("installedAt".to_string(), Value::from(now.clone()));
+    record.insert("lastUpdated".to_string(), Value::from(now));
+    if let Some(sha) = &git_commit_sha {
+        record.insert("gitCommitSha".to_string(), Value::from(sha.clone()));
+    }
+    record.insert("isLocal".to_string(), Value::from(matches!(entry.source, PluginSource::Path(_))));
+    touched.push(record_install(&id, record)?);
+
+    if enable {
+        touched.push(claude_config::set_plugin_enabled(&claude_config::user_settings_path()?, &id, true)?);
+    }
+
+    let mut files: Vec<String> = files.iter()
+        .map(|f| f.path.to_string_lossy().replace('\\', "/"))
+        .collect();
+    files.sort();
+
+    Ok(PluginInstallReport {
+        id,
+        name: entry.name.clone(),
+        marketplace: marketplace_name,
+        version,
+        install_path,
+        git_commit_sha,
+        files,
+        touched,
+        conflicts,
+    })
+}
+
+#[tauri::command]
+pub async fn install_plugin(
+    marketplace: String,
+    plugin: String,
+    enable: Option<bool>,
+    stop_on_conflict: Option<bool>,
+) -> Result<PluginInstallReport, String> {
+    let enable = enable.unwrap_or(true);
+    let stop_on_conflict = stop_on_conflict.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || install(&marketplace, &plugin, enable, stop_on_conflict))
+        .await
+        .map_err(|e| format!("Plugin install failed: {}", e))?
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginUninstallReport {
+    pub id: String,
+    pub scope: SettingsScope,
+    pub dry_run: bool,
+    // Plugin folders that were (or with dry_run, would be) deleted
+    pub removed_paths: Vec<String>,
+    // Config files that changed; keys name the entries that went, e.g. "mcpServers.db"
+    pub touched: Vec<TouchedFile>,
+    // Entries named or configured like the plugin's own that don't point at its files. They may
+    // be the user's, so they are reported and left in place.
+    pub kept: Vec<KeptEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeptEntry {
+    pub path: String,
+    pub key: String,
+}
+
+// What an installed plugin added, so copies of it left in Claude Code's config can be found
+struct Contributions {
+    install_paths: Vec<String>,
+    hooks: Vec<(String, String)>,
+    servers: Vec<McpServerInfo>,
+}
+
+// Keys of entries a cleanup removed, and of look-alikes it left alone
+#[derive(Default)]
+struct Cleanup {
+    removed: Vec<String>,
+    kept: Vec<String>,
+}
+
+const PLUGIN_ROOT_VAR: &str = "${CLAUDE_PLUGIN_ROOT}";
+
+impl Contributions {
+    fn mentions_plugin(&self, text: &str) -> bool {
+        self.install_paths.iter().any(|path| text.contains(path.as_str()))
+    }
+
+    fn defines_hook(&self, event: &str, command: &str) -> bool {
+        self.hooks.iter().any(|(e, c)| {
+            e == event && (c == command || self.install_paths.iter().any(|path| c.replace(PLUGIN_ROOT_VAR, path) == command))
+        })
+    }
+
+    // Only hooks pointing at the plugin's files are its own: through the install path, or
+    // through ${CLAUDE_PLUGIN_ROOT} in a command the plugin itself defines
+    fn owns_hook(&self, event: &str, command: &str) -> bool {
+        self.mentions_plugin(command) || (command.contains(PLUGIN_ROOT_VAR) && self.defines_hook(event, command))
+    }
+
+    fn defines_server(&self, name: &str) -> bool {
+        self.servers.iter().any(|server| server.name == name)
+    }
+
+    fn owns_server(&self, name: &str, config: &Value) -> bool {
+        let text = config.to_string();
+        self.mentions_plugin(&text) || (text.contains(PLUGIN_ROOT_VAR) && self.defines_server(name))
+    }
+
+    fn remove_hooks(&self, settings: &mut serde_json::Map<String, Value>, cleanup: &mut Cleanup) {
+        let Some(Value::Object(events)) = settings.get_mut("hooks") else {
+            return;
+        };
+
+        let mut emptied_events = Vec::new();
+        for (event, groups) in events.iter_mut() {
+            let Some(groups) = groups.as_array_mut() else {
+                continue;
+            };
+            let mut emptied_groups = Vec::new();
+            for (index, group) in groups.iter_mut().enumerate() {
+                let Some(entries) = group.get_mut("hooks").and_then(Value::as_array_mut) else {
+                    continue;
+                };
+                let before = entries.len();
+                entries.retain(|hook| {
+                    let Some(command) = hook.get("command").and_then(Value::as_str) else {
+                        return true;
+                    };
+                    if self.owns_hook(event, command) {
+                        return false;
+                    }
+                    if self.defines_hook(event, command) {
+                        cleanup.kept.push(format!("hooks.{}", event));
+                    }
+                    true
+                });
+                if entries.len() != before {
+                    cleanup.removed.push(format!("hooks.{}", event));
+                    if entries.is_empty() {
+                        emptied_groups.push(index);
+                    }
+                }
+            }
+
+            // Groups and events are dropped only where this uninstall emptied them
+            if !emptied_groups.is_empty() {
+                let mut index = 0;
+                groups.retain(|_| {
+                    let keep = !emptied_groups.contains(&index);
+                    index += 1;
+                    keep
+                });
+                if groups.is_empty() {
+                    emptied_events.push(event.clone());
+                }
+            }
+        }
+
+        if !emptied_events.is_empty() {
+            events.retain(|event, _| !emptied_events.contains(event));
+            if events.is_empty() {
+                settings.remove("hooks");
+            }
+        }
+    }
+
+    fn remove_servers(&self, servers: Option<&mut Value>, prefix: &str, cleanup: &mut Cleanup) {
+        let Some(Value::Object(servers)) = servers else {
+            return;
+        };
+        servers.retain(|name, config| {
+            let key = format!("{}.{}", prefix, name);
+            if self.owns_server(name, config) {
+                cleanup.removed.push(key);
+                return false;
+            }
+            if self.defines_server(name) {
+                cleanup.kept.push(key);
+            }
+            true
+        });
+    }
+}
+
+// Applies `update` to an existing config file, reporting the keys it says it removed and
+// adding the look-alikes it left alone to `kept`
+fn clean_file<F>(path: &Path, dry_run: bool, kept: &mut Vec<KeptEntry>, update: F) -> Result<Option<TouchedFile>, String>
+where
+    F: FnOnce(&mut serde_json::Map<String, Value>, &mut Cleanup),
+{
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let mut cleanup = Cleanup::default();
+    let mut touched = claude_config::update_json_with(path, Vec::new(), dry_run, |object| {
+        update(object, &mut cleanup);
+        Ok(())
+    })?;
+
+    cleanup.kept.dedup();
+    for key in cleanup.kept {
+        kept.push(KeptEntry { path: clients::path_to_string(path)?, key });
+    }
+    if touched.action == FileAction::Unchanged {
+        return Ok(None);
+    }
+    cleanup.removed.dedup();
+    touched.keys = cleanup.removed;
+    Ok(Some(touched))
+}
+
+// Whether a record from installed_plugins.json belongs to `scope` (and, below user scope, to
+// `project`). Version 1 records have no scope and are user installs.
+fn record_matches(record: &serde_json::Map<String, Value>, scope: SettingsScope, project: Option<&str>) -> bool {
+    let record_scope = record.get("scope").and_then(Value::as_str).unwrap_or("user");
+    record_scope == scope.as_str()
+        && (scope == SettingsScope::User || record.get("projectPath").and_then(Value::as_str) == project)
+}
+
+// Drops the records of `id` that match, and the plugin's entry once none are left
+fn remove_records(plugins: &mut serde_json::Map<String, Value>, id: &str, scope: SettingsScope, project: Option<&str>) -> bool {
+    let emptied = match plugins.get_mut(id) {
+        Some(Value::Array(records)) => {
+            let before = records.len();
+            records.retain(|r| !r.as_object().map(|r| record_matches(r, scope, project)).unwrap_or(false));
+            if records.len() == before {
+                return false;
+            }
+            records.is_empty()
+        }
+        Some(Value::Object(record)) if record_matches(record, scope, project) => true,
+        _ => return false,
+    };
+    if emptied {
+        plugins.remove(id);
+    }
+    true
+}
+
+// Removes a plugin from one scope the way `/plugin uninstall` does, and also takes out the MCP
+// servers and hooks it contributed that ended up copied into that scope's config files.
+// Records for other scopes and projects stay, and so does any cached copy they still use.
+pub fn uninstall(id: &str, scope: SettingsScope, project_path: Option<&str>, dry_run: bool) -> Result<PluginUninstallReport, String> {
+    let settings_path = plugin_settings::settings_path(scope, project_path)?;
+    let project_dir = project_path.map(clients::expand_home).transpose()?;
+    let project_key = project_dir.as_ref().map(|p| p.to_string_lossy().to_string());
+    let project_key = if scope == SettingsScope::User { None } else { project_key };
+
+    let in_scope = |r: &InstalledPlugin| {
+        r.scope == scope.as_str() && (scope == SettingsScope::User || r.project_path == project_key)
+    };
+    let (records, remaining): (Vec<InstalledPlugin>, Vec<InstalledPlugin>) = load_installed()?
+        .into_iter()
+        .filter(|r| r.id == id)
+        .partition(|r| in_scope(r));
+    let mentioned = claude_config::read_json(&settings_path)?
+        .map(|settings| settings.get("enabledPlugins").and_then(|p| p.get(id)).is_some())
+        .unwrap_or(false);
+    if records.is_empty() && !mentioned {
+        return Err(format!("Plugin {} is not installed at {} scope", id, scope.as_str()));
+    }
+
+    let cache = claude_config::plugins_dir()?.join(CACHE_DIR);
+    let mut contributions = Contributions { install_paths: Vec::new(), hooks: Vec::new(), servers: Vec::new() };
+    let mut removable = Vec::new();
+    for path in records.iter().filter_map(|r| r.install_path.as_deref()) {
+        let dir = PathBuf::from(path);
+        contributions.install_paths.push(path.to_string());
+        if let Ok(inventory) = plugin_inspect::inspect_path(&dir) {
+            contributions.hooks.extend(inventory.hooks.into_iter().filter_map(|h| Some((h.event, h.command?))));
+            contributions.servers.extend(inventory.mcp_servers);
+        }
+        // Plugins installed straight from a local folder point at that folder; it isn't ours to
+        // delete. A cached copy another scope or project still uses stays as well.
+        let shared = remaining.iter().any(|r| r.install_path.as_deref() == Some(path));
+        if dir.starts_with(&cache) && !shared && fs::symlink_metadata(&dir).is_ok() && !removable.contains(&dir) {
+            removable.push(dir);
+        }
+    }
+
+    let mut touched = Vec::new();
+    let mut kept = Vec::new();
+    let key = id.to_string();
+    touched.extend(clean_file(&installed_plugins_path()?, dry_run, &mut kept, |root, cleanup| {
+        if let Some(Value::Object(plugins)) = root.get_mut("plugins") {
+            if remove_records(plugins, &key, scope, project_key.as_deref()) {
+                cleanup.removed.push(format!("plugins.{}", key));
+            }
+        }
+    })?);
+
+    touched.extend(clean_file(&settings_path, dry_run, &mut kept, |settings, cleanup| {
+        if let Some(Value::Object(enabled)) = settings.get_mut("enabledPlugins") {
+            if enabled.remove(&key).is_some() {
+                cleanup.removed.push(format!("enabledPlugins.{}", key));
+            }
+        }
+        contributions.remove_hooks(settings, cleanup);
+    })?);
+
+    // Settings paths above already required a project for project and local scope
+    match (scope, &project_dir, &project_key) {
+        (SettingsScope::Project, Some(project), _) => {
+            touched.extend(clean_file(&project.join(".mcp.json"), dry_run, &mut kept, |config, cleanup| {
+                contributions.remove_servers(config.get_mut("mcpServers"), "mcpServers", cleanup);
+            })?);
+        }
+        (SettingsScope::Local, _, Some(project)) => {
+            touched.extend(clean_file(&claude_config::user_state_path()?, dry_run, &mut kept, |state, cleanup| {
+                let prefix = format!("projects.{}.mcpServers", project);
+                if let Some(entry) = state.get_mut("projects").and_then(|p| p.get_mut(project)) {
+                    contributions.remove_servers(entry.get_mut("mcpServers"), &prefix, cleanup);
+                }
+            })?);
+        }
+        _ => {
+            touched.extend(clean_file(&claude_config::user_state_path()?, dry_run, &mut kept, |state, cleanup| {
+                contributions.remove_servers(state.get_mut("mcpServers"), "mcpServers", cleanup);
+            })?);
+        }
+    }
+
+    let mut removed_paths = Vec::new();
+    for dir in removable {
+        if !dry_run {
+            installer::remove_path(&dir)?;
+            // cache/<marketplace>/<plugin> goes too once no version is left in it
+            if let Some(parent) = dir.parent().filter(|p| p.starts_with(&cache)) {
+                let _ = fs::remove_dir(parent);
+            }
+        }
+        removed_paths.push(clients::path_to_string(&dir)?);
+    }
+
+    Ok(PluginUninstallReport { id: id.to_string(), scope, dry_run, removed_paths, touched, kept })
+}
+
+// Scope defaults to user; project and local scope need the project path
+#[tauri::command]
+pub async fn uninstall_plugin(
+    plugin_id: String,
+    scope: Option<SettingsScope>,
+    project_path: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<PluginUninstallReport, String> {
+    let scope = scope.unwrap_or(SettingsScope::User);
+    let dry_run = dry_run.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || uninstall(&plugin_id, scope, project_path.as_deref(), dry_run))
+        .await
+        .map_err(|e| format!("Plugin uninstall failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const INSTALL_PATH: &str = "/plugins/cache/demo/hello/1.0.0";
+
+    fn object(value: Value) -> serde_json::Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    fn file(path: &str) -> SkillFile {
+        SkillFile { path: PathBuf::from(path), contents: Vec::new(), mode: None }
+    }
+
+    fn paths(files: &[SkillFile]) -> Vec<String> {
+        files.iter().map(|f| f.path.to_string_lossy().replace('\\', "/")).collect()
+    }
+
+    fn server(name: &str) -> McpServerInfo {
+        McpServerInfo {
+            name: name.to_string(),
+            transport: "stdio".to_string(),
+            command: None,
+            args: Vec::new(),
+            url: None,
+            env: Vec::new(),
+            file: ".mcp.json".to_string(),
+        }
+    }
+
+    fn contributions() -> Contributions {
+        Contributions {
+            install_paths: vec![INSTALL_PATH.to_string()],
+            hooks: vec![
+                ("SessionStart".to_string(), "${CLAUDE_PLUGIN_ROOT}/start.sh".to_string()),
+                ("PreToolUse".to_string(), "npx lint".to_string()),
+            ],
+            servers: vec![server("db"), server("api")],
+        }
+    }
+
+    #[test]
+    fn strip_wrapper_removes_a_shared_top_folder() {
+        let files = strip_wrapper(vec![file("pkg/.claude-plugin/plugin.json"), file("pkg/README.md")]);
+        assert_eq!(paths(&files), [".claude-plugin/plugin.json", "README.md"]);
+    }
+
+    #[test]
+    fn strip_wrapper_keeps_unwrapped_files() {
+        let files = strip_wrapper(vec![file("pkg/a.md"), file("other/b.md")]);
+        assert_eq!(paths(&files), ["pkg/a.md", "other/b.md"]);
+
+        let files = strip_wrapper(vec![file("pkg/a.md"), file("README.md")]);
+        assert_eq!(paths(&files), ["pkg/a.md", "README.md"]);
+
+        assert!(strip_wrapper(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn add_record_starts_new_files_in_version_2() {
+        let mut root = serde_json::Map::new();
+        add_record(&mut root, "hello@demo".to_string(), object(json!({"scope": "user", "version": "1.0.0"}))).unwrap();
+
+        assert_eq!(Value::Object(root), json!({
+            "version": 2,
+            "plugins": {"hello@demo": [{"scope": "user", "version": "1.0.0"}]}
+        }));
+    }
+
+    #[test]
+    fn add_record_updates_the_user_record_in_version_2() {
+        let mut root = object(json!({
+            "version": 2,
+            "plugins": {"hello@demo": [
+                {"scope": "local", "projectPath": "/p", "version": "0.9.0"},
+                {"scope": "user", "version": "0.9.0", "installedAt": "then"}
+            ]}
+        }));
+        let record = json!({"scope": "user", "version": "1.0.0", "installedAt": "now"});
+        add_record(&mut root, "hello@demo".to_string(), object(record)).unwrap();
+
+        assert_eq!(root["plugins"]["hello@demo"], json!([
+            {"scope": "local", "projectPath": "/p", "version": "0.9.0"},
+            {"scope": "user", "version": "1.0.0", "installedAt": "then"}
+        ]));
+    }
+
+    #[test]
+    fn add_record_keeps_version_1_files_flat() {
+        let mut root = object(json!({"version": 1, "plugins": {"other@demo": {"version": "2.0.0"}}}));
+        add_record(&mut root, "hello@demo".to_string(), object(json!({"scope": "user", "version": "1.0.0"}))).unwrap();
+        assert_eq!(root["plugins"]["hello@demo"], json!({"version": "1.0.0"}));
+
+        add_record(&mut root, "hello@demo".to_string(), object(json!({"version": "1.1.0"}))).unwrap();
+        assert_eq!(root["plugins"]["hello@demo"], json!({"version": "1.1.0"}));
+        assert_eq!(root["plugins"]["other@demo"], json!({"version": "2.0.0"}));
+    }
+
+    fn git(args: &[&str], cwd: &Path) {
+        let mut full = vec!["-c", "user.name=Skiller", "-c", "user.email=skiller@example.com"];
+        full.extend(args);
+        marketplace::git(&full, Some(cwd)).unwrap();
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    // A marketplace repository served from a local git repository instead of GitHub
+    fn local_marketplace(root: &Path) -> PathBuf {
+        let repo = root.join("market");
+        write(&repo.join(marketplace::MARKETPLACE_FILE), &json!({
+            "name": "local-test",
+            "owner": {"name": "Skiller"},
+            "plugins": [{"name": "hello", "source": "./plugins/hello"}]
+        }).to_string());
+        let plugin = repo.join("plugins/hello");
+        write(&plugin.join(PLUGIN_MANIFEST), &json!({"name": "hello", "version": "1.2.0"}).to_string());
+        write(&plugin.join("commands/hi.md"), "Say hi");
+        write(&plugin.join("scripts/hook.sh"), "#!/bin/sh\necho hi\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(plugin.join("scripts/hook.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        git(&["init", "-q"], &repo);
+        git(&["add", "-A"], &repo);
+        git(&["commit", "-q", "-m", "Add hello"], &repo);
+        repo
+    }
+
+    #[test]
+    fn install_from_a_git_marketplace_registers_and_enables_the_plugin() {
+        let _lock = claude_config::CONFIG_DIR_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let root = std::env::temp_dir().join(format!("skiller-test-{}-plugin-install", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let repo = local_marketplace(&root);
+        std::env::set_var("CLAUDE_CONFIG_DIR", root.join("config"));
+
+        let source = format!("file://{}", repo.display());
+        let report = install(&source, "hello", true, false);
+        let read = |path: &str| claude_config::read_json(&root.join("config").join(path)).unwrap().unwrap_or_default();
+        let (installed, settings, known) = (read("plugins/installed_plugins.json"), read("settings.json"), read("plugins/known_marketplaces.json"));
+        let install_path = root.join("config/plugins/cache/local-test/hello/1.2.0");
+        let hook = fs::metadata(install_path.join("scripts/hook.sh"));
+        std::env::remove_var("CLAUDE_CONFIG_DIR");
+        let _ = fs::remove_dir_all(&root);
+
+        let report = report.unwrap();
+        assert_eq!(report.id, "hello@local-test");
+        assert_eq!(report.version, "1.2.0");
+        assert_eq!(report.install_path, install_path.to_string_lossy());
+        assert_eq!(report.files, [".claude-plugin/plugin.json", "commands/hi.md", "scripts/hook.sh"]);
+        assert_eq!(installed["plugins"]["hello@local-test"][0]["installPath"], json!(report.install_path));
+        assert_eq!(installed["plugins"]["hello@local-test"][0]["scope"], json!("user"));
+        assert_eq!(settings["enabledPlugins"]["hello@local-test"], json!(true));
+        assert!(known.get("local-test").is_some());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(hook.unwrap().permissions().mode() & 0o777, 0o755);
+        }
+        #[cfg(not(unix))]
+        assert!(hook.is_ok());
+    }
+
+    #[test]
+    fn remove_hooks_takes_out_only_the_plugins_hooks() {
+        let mut settings = object(json!({
+            "model": "opus",
+            "hooks": {
+                "SessionStart": [
+                    {"hooks": [{"type": "command", "command": format!("{}/start.sh", INSTALL_PATH)}]},
+                    {"hooks": [
+                        {"type": "command", "command": "${CLAUDE_PLUGIN_ROOT}/start.sh"},
+                        {"type": "command", "command": "echo mine"}
+                    ]},
+                    {"hooks": []}
+                ],
+                "Stop": [{"hooks": [{"type": "command", "command": format!("{}/stop.sh", INSTALL_PATH)}]}],
+                "PreToolUse": [{"matcher": "Bash", "hooks": [{"type": "command", "command": "npx lint"}]}]
+            }
+        }));
+        let mut cleanup = Cleanup::default();
+        contributions().remove_hooks(&mut settings, &mut cleanup);
+
+        assert_eq!(Value::Object(settings), json!({
+            "model": "opus",
+            "hooks": {
+                "SessionStart": [
+                    {"hooks": [{"type": "command", "command": "echo mine"}]},
+                    {"hooks": []}
+                ],
+                "PreToolUse": [{"matcher": "Bash", "hooks": [{"type": "command", "command": "npx lint"}]}]
+            }
+        }));
+        assert_eq!(cleanup.removed, ["hooks.SessionStart", "hooks.SessionStart", "hooks.Stop"]);
+        assert_eq!(cleanup.kept, ["hooks.PreToolUse"]);
+    }
+
+    #[test]
+    fn remove_hooks_drops_the_hooks_key_once_empty() {
+        let mut settings = object(json!({
+            "hooks": {"Stop": [{"hooks": [{"type": "command", "command": format!("{}/stop.sh", INSTALL_PATH)}]}]}
+        }));
+        contributions().remove_hooks(&mut settings, &mut Cleanup::default());
+        assert!(settings.is_empty());
+    }
+
+    #[test]
+    fn remove_servers_keeps_look_alikes() {
+        let mut servers = json!({
+            "db": {"command": "${CLAUDE_PLUGIN_ROOT}/bin/db"},
+            "search": {"command": format!("{}/bin/search", INSTALL_PATH)},
+            "api": {"type": "http", "url": "https://example.com/mcp"},
+            "mine": {"command": "npx mine"}
+        });
+        let mut cleanup = Cleanup::default();
+        contributions().remove_servers(Some(&mut servers), "mcpServers", &mut cleanup);
+
+        assert_eq!(servers, json!({
+            "api": {"type": "http", "url": "https://example.com/mcp"},
+            "mine": {"command": "npx mine"}
+        }));
+        assert_eq!(cleanup.removed, ["mcpServers.db", "mcpServers.search"]);
+        assert_eq!(cleanup.kept, ["mcpServers.api"]);
+    }
+
+    #[test]
+    fn remove_records_only_touches_the_requested_scope() {
+        let mut plugins = object(json!({
+            "hello@demo": [
+                {"scope": "user"},
+                {"scope": "local", "projectPath": "/p"},
+                {"scope": "local", "projectPath": "/q"}
+            ]
+        }));
+
+        assert!(!remove_records(&mut plugins, "hello@demo", SettingsScope::Project, Some("/p")));
+        assert!(remove_records(&mut plugins, "hello@demo", SettingsScope::Local, Some("/p")));
+        assert_eq!(plugins["hello@demo"], json!([{"scope": "user"}, {"scope": "local", "projectPath": "/q"}]));
+
+        assert!(remove_records(&mut plugins, "hello@demo", SettingsScope::User, None));
+        assert!(remove_records(&mut plugins, "hello@demo", SettingsScope::Local, Some("/q")));
+        assert!(plugins.is_empty());
+        assert!(!remove_records(&mut plugins, "hello@demo", SettingsScope::User, None));
+    }
+
+    #[test]
+    fn remove_records_treats_version_1_records_as_user_installs() {
+        let mut plugins = object(json!({"hello@demo": {"version": "1.0.0"}}));
+
+        assert!(!remove_records(&mut plugins, "hello@demo", SettingsScope::Local, Some("/p")));
+        assert!(plugins.contains_key("hello@demo"));
+        assert!(remove_records(&mut plugins, "hello@demo", SettingsScope::User, None));
+        assert!(plugins.is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+use crate::clients::{self, Client, SkillType, ALL_CLIENTS};
+use crate::inventory;
+
+// Folder names clients use for subagents and slash commands inside their config
+// directory (OpenCode uses the singular forms, Copilot calls commands "prompts")
+const AGENT_DIRS: [&str; 2] = ["agents", "agent"];
+const COMMAND_DIRS: [&str; 3] = ["commands", "command", "prompts"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectClient {
+    pub client: Client,
+    pub configured: bool,
+    // Markers found, relative to the project root
+    pub markers: Vec<String>,
+    pub skill_dir: String,
+    pub skills: Vec<String>,
+    pub agents: Vec<String>,
+    pub commands: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectInfo {
+    pub path: String,
+    pub is_git_repo: bool,
+    pub git_root: Option<String>,
+    pub clients: Vec<ProjectClient>,
+    // Clients to preselect as install targets
+    pub suggested_clients: Vec<Client>,
+}
+
+// `.git` is a directory in a normal checkout and a file in worktrees and submodules
+pub fn find_git_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(|dir| dir.to_path_buf())
+}
+
+// Markdown definitions in the first of `dirs` that exists, by name without extensions
+// ("review.prompt.md" -> "review")
+fn list_definitions(config_dir: &Path, dirs: &[&str]) -> Vec<String> {
+    let Some(dir) = dirs.iter().map(|d| config_dir.join(d)).find(|d| d.is_dir()) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with('.') || !file_name.ends_with(".md") {
+                return None;
+            }
+            file_name.split('.').next().map(|n| n.to_string())
+        })
+        .collect();
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn inspect_client(project: &Path, client: Client, skill_dir: &str, roots: &[inventory::SkillRoot]) -> ProjectClient {
+    let markers: Vec<String> = client.project_markers()
+        .iter()
+        .filter(|m| project.join(m).exists())
+        .map(|m| m.to_string())
+        .collect();
+
+    let skills = roots.iter()
+        .find(|r| r.skill_type == SkillType::Project && r.clients.contains(&client))
+        .map(|root| inventory::discover_in_root(root).into_iter().map(|s| s.name).collect())
+        .unwrap_or_default();
+
+    let config_dir = Path::new(skill_dir)
+        .parent()
+        .map(|dir| project.join(dir))
+        .unwrap_or_else(|| project.to_path_buf());
+
+    ProjectClient {
+        client,
+        configured: !markers.is_empty(),
+        markers,
+        skill_dir: skill_dir.to_string(),
+        skills,
+        agents: list_definitions(&config_dir, &AGENT_DIRS),
+        commands: list_definitions(&config_dir, &COMMAND_DIRS),
+    }
+}
+
+pub fn inspect(path: &Path) -> Result<ProjectInfo, String> {
+    if !path.is_dir() {
+        return Err(format!("{} is not a directory", path.display()));
+    }
+
+    let project_path = clients::path_to_string(path)?;
+    let roots = inventory::skill_roots(std::slice::from_ref(&project_path));
+
+    let clients: Vec<ProjectClient> = ALL_CLIENTS.iter()
+        .filter_map(|&client| {
+            let skill_dir = client.project_skill_dir()?;
+            Some(inspect_client(path, client, skill_dir, &roots))
+        })
+        .collect();
+
+    let suggested_clients = clients.iter()
+        .filter(|c| c.configured || !c.skills.is_empty())
+        .map(|c| c.client)
+        .collect();
+
+    let git_root = find_git_root(path);
+    Ok(ProjectInfo {
+        path: project_path,
+        is_git_repo: git_root.is_some(),
+        git_root: git_root.as_deref().map(clients::path_to_string).transpose()?,
+        clients,
+        suggested_clients,
+    })
+}
+
+#[tauri::command]
+pub async fn inspect_project(path: String) -> Result<ProjectInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || inspect(&clients::expand_home(&path)?))
+        .await
+        .map_err(|e| format!("Project inspection failed: {}", e))?
+}
@@ -0,0 +1,404 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use serde::Deserialize;
+
+const GITHUB_RAW_PREFIX: &str = "https://raw.githubusercontent.com/";
+const GITHUB_API_BASE: &str = "https://api.github.com";
+const USER_AGENT: &str = concat!("Skiller/", env!("CARGO_PKG_VERSION"));
+
+pub const SKILL_FILE: &str = "SKILL.md";
+// Subfolders taken along when SKILL.md sits at the root of a repository
+const ROOT_SKILL_DIRS: [&str; 3] = ["scripts", "references", "assets"];
+
+pub enum Conditional {
+    NotModified,
+    NotFound,
+    Modified {
+        bytes: Vec<u8>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteFile {
+    pub path: String,
+    pub url: String,
+}
+
+struct GithubLocation {
+    owner: String,
+    repo: String,
+    git_ref: String,
+    dir: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GithubEntry {
+    name: String,
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+    download_url: Option<String>,
+}
+
+// Contents API listings by URL, with the ETag they came with. GitHub answers a matching
+// If-None-Match with 304, which doesn't count against the rate limit.
+static LISTINGS: Mutex<BTreeMap<String, CachedListing>> = Mutex::new(BTreeMap::new());
+
+#[derive(Clone)]
+struct CachedListing {
+    etag: String,
+    entries: Vec<GithubEntry>,
+}
+
+// Unauthenticated API calls are limited to 60 an hour; a token raises that to 5000
+fn github_token() -> Option<String> {
+    ["GITHUB_TOKEN", "GH_TOKEN"].iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|token| !token.trim().is_empty())
+}
+
+pub fn http_client() -> Result<reqwest::blocking::Client, String> {
+    reqwest::blocking::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+pub fn fetch_bytes(url: &str) -> Result<Vec<u8>, String> {
+    let response = http_client()?
+        .get(url)
+        .send()
+        .map_err(|e| format!("Failed to download: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Download failed with status: {}", response.status()));
+    }
+
+    response.bytes()
+        .map(|b| b.to_vec())
+        .map_err(|e| format!("Failed to read response: {}", e))
+}
+
+pub fn fetch_conditional(url: &str, etag: Option<&str>, last_modified: Option<&str>) -> Result<Conditional, String> {
+    let mut request = http_client()?.get(url);
+    if let Some(etag) = etag {
+        request = request.header("If-None-Match", etag);
+    }
+    if let Some(last_modified) = last_modified {
+        request = request.header("If-Modified-Since", last_modified);
+    }
+
+    let response = request.send()
+        .map_err(|e| format!("Failed to download: {}", e))?;
+
+    match response.status() {
+        reqwest::StatusCode::NOT_MODIFIED => return Ok(Conditional::NotModified),
+        reqwest::StatusCode::NOT_FOUND => return Ok(Conditional::NotFound),
+        status if !status.is_success() => {
+            return Err(format!("Download failed with status: {}", status));
+        }
+        _ => {}
+    }
+
+    let header = |name: &str| {
+        response.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    let etag = header("ETag");
+    let last_modified = header("Last-Modified");
+
+    let bytes = response.bytes()
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    Ok(Conditional::Modified {
+        bytes: bytes.to_vec(),
+        etag,
+        last_modified,
+    })
+}
+
+// URL of a file next to SKILL.md, e.g. "scripts/run.sh" relative to the raw SKILL.md URL
+pub fn sibling_url(raw_file_url: &str, relative: &str) -> Result<String, String> {
+    let base = reqwest::Url::parse(raw_file_url)
+        .map_err(|e| format!("Invalid skill URL {}: {}", raw_file_url, e))?;
+    base.join(relative)
+        .map(|u| u.to_string())
+        .map_err(|e| format!("Invalid skill file path {}: {}", relative, e))
+}
+
+// raw.githubusercontent.com/<owner>/<repo>/<ref>/<dir...>/SKILL.md. A ref can contain
+// slashes ("feature/x"), so every split between ref and dir is a candidate, shortest ref first.
+fn github_candidates(url: &str) -> Vec<GithubLocation> {
+    let Some(rest) = url.strip_prefix(GITHUB_RAW_PREFIX) else {
+        return Vec::new();
+    };
+    let parts: Vec<&str> = rest.split('/').collect();
+    if parts.len() < 4 || parts.iter().any(|p| p.is_empty()) || parts[parts.len() - 1] != SKILL_FILE {
+        return Vec::new();
+    }
+
+    let middle = &parts[2..parts.len() - 1];
+    (1..=middle.len())
+        .map(|split| GithubLocation {
+            owner: parts[0].to_string(),
+            repo: parts[1].to_string(),
+            git_ref: middle[..split].join("/"),
+            dir: middle[split..].join("/"),
+        })
+        .collect()
+}
+
+// One directory of the contents API; None when the ref or directory doesn't exist
+fn github_entries(
+    client: &reqwest::blocking::Client,
+    location: &GithubLocation,
+    dir: &str,
+) -> Result<Option<Vec<GithubEntry>>, String> {
+    let url = format!(
+        "{}/repos/{}/{}/contents/{}?ref={}",
+        GITHUB_API_BASE, location.owner, location.repo, dir, location.git_ref
+    );
+    fetch_listing(client, &url, github_token().as_deref())
+}
+
+// One contents API request; None when the ref or directory doesn't exist
+fn fetch_listing(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    token: Option<&str>,
+) -> Result<Option<Vec<GithubEntry>>, String> {
+    let cached = LISTINGS.lock()
+        .map_err(|_| "Listing cache is unavailable".to_string())?
+        .get(url)
+        .cloned();
+
+    let mut request = client.get(url).header("Accept", "application/vnd.github+json");
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    if let Some(cached) = &cached {
+        request = request.header("If-None-Match", &cached.etag);
+    }
+    let response = request.send()
+        .map_err(|e| format!("Failed to list skill files: {}", e))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_MODIFIED {
+        if let Some(cached) = cached {
+            return Ok(Some(cached.entries));
+        }
+    }
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let rate_limited = response.headers()
+        .get("x-ratelimit-remaining")
+        .map(|remaining| remaining == "0")
+        .unwrap_or(false);
+    if rate_limited && token.is_none() {
+        return Err("GitHub API rate limit reached; set GITHUB_TOKEN to list skill files".to_string());
+    }
+    if !status.is_success() {
+        return Err(format!("Listing skill files failed with status: {}", status));
+    }
+
+    let etag = response.headers()
+        .get("ETag")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let body = response.bytes()
+        .map_err(|e| format!("Failed to read skill file listing: {}", e))?;
+    // A file path returns a single object rather than a list
+    let Ok(entries) = serde_json::from_slice::<Vec<GithubEntry>>(&body) else {
+        return Ok(None);
+    };
+
+    if let (Some(etag), Ok(mut listings)) = (etag, LISTINGS.lock()) {
+        listings.insert(url.to_string(), CachedListing { etag, entries: entries.clone() });
+    }
+    Ok(Some(entries))
+}
+
+// Asks the API which split of the URL names a ref whose directory really holds SKILL.md
+fn resolve_github(
+    client: &reqwest::blocking::Client,
+    raw_file_url: &str,
+    candidates: Vec<GithubLocation>,
+) -> Result<(GithubLocation, Vec<GithubEntry>), String> {
+    for location in candidates {
+        let Some(entries) = github_entries(client, &location, &location.dir)? else { continue };
+        if entries.iter().any(|e| e.kind == "file" && e.name == SKILL_FILE) {
+            return Ok((location, entries));
+        }
+    }
+    Err(format!("{} not found at {}", SKILL_FILE, raw_file_url))
+}
+
+fn collect_github_files(
+    client: &reqwest::blocking::Client,
+    location: &GithubLocation,
+    entries: Vec<GithubEntry>,
+    prefix: &str,
+    files: &mut Vec<RemoteFile>,
+) -> Result<(), String> {
+    for entry in entries {
+        let relative = format!("{}{}", prefix, entry.name);
+        match entry.kind.as_str() {
+            "file" => {
+                let Some(download_url) = entry.download_url else { continue };
+                files.push(RemoteFile { path: relative, url: download_url });
+            }
+            // A skill at the repository root shares it with everything else in the repo,
+            // so only its conventional resource folders come along
+            "dir" if !location.dir.is_empty() || !prefix.is_empty() || ROOT_SKILL_DIRS.contains(&entry.name.as_str()) => {
+                let entries = github_entries(client, location, &entry.path)?
+                    .ok_or_else(|| format!("Skill folder {} disappeared while listing", entry.path))?;
+                collect_github_files(client, location, entries, &format!("{}/", relative), files)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Resolves every file that belongs to the skill whose SKILL.md lives at `raw_file_url`.
+// GitHub-hosted skills are listed through the contents API so bundled scripts and
+// references come along; anything else is just SKILL.md.
+pub fn list_skill_files(raw_file_url: &str) -> Result<Vec<RemoteFile>, String> {
+    let candidates = github_candidates(raw_file_url);
+    if candidates.is_empty() {
+        return Ok(vec![RemoteFile {
+            path: SKILL_FILE.to_string(),
+            url: raw_file_url.to_string(),
+        }]);
+    }
+
+    let client = http_client()?;
+    let (location, entries) = resolve_github(&client, raw_file_url, candidates)?;
+    let mut files = Vec::new();
+    collect_github_files(&client, &location, entries, "", &mut files)?;
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use crate::installer;
+
+    type Requests = Arc<Mutex<Vec<String>>>;
+
+    fn response(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+        let headers: String = headers.iter().map(|(name, value)| format!("{}: {}\r\n", name, value)).collect();
+        format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+            status, body.len(), headers, body
+        )
+    }
+
+    // A local stand-in for GitHub: answers each connection with the next canned response and
+    // keeps the request heads so tests can check what was sent
+    fn serve(responses: Vec<String>) -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requests: Requests = Arc::default();
+        let seen = requests.clone();
+        std::thread::spawn(move || {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept() else { return };
+                let mut head = Vec::new();
+                let mut byte = [0u8; 1];
+                while !head.ends_with(b"\r\n\r\n") && stream.read(&mut byte).map(|n| n == 1).unwrap_or(false) {
+                    head.push(byte[0]);
+                }
+                seen.lock().unwrap().push(String::from_utf8_lossy(&head).to_lowercase());
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        (base, requests)
+    }
+
+    #[test]
+    fn skills_outside_github_install_from_their_skill_md() {
+        let skill = "---\nname: pdf\ndescription: Fill PDF forms\n---\nUse it.\n";
+        let (base, requests) = serve(vec![response("200 OK", &[], skill), response("404 Not Found", &[], "")]);
+        let url = format!("{}/acme/pdf/SKILL.md", base);
+
+        let files = installer::fetch_skill_files(&url).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, std::path::Path::new(SKILL_FILE));
+        installer::validate_skill_files(&files).unwrap();
+
+        let target = std::env::temp_dir().join(format!("skiller-test-{}-remote-install/pdf", std::process::id()));
+        installer::install_files(&target, &files).unwrap();
+        let installed = std::fs::read_to_string(target.join(SKILL_FILE)).unwrap();
+        let _ = std::fs::remove_dir_all(target.parent().unwrap());
+        assert_eq!(installed, skill);
+        assert!(requests.lock().unwrap()[0].starts_with("get /acme/pdf/skill.md "));
+
+        assert!(installer::fetch_skill_files(&url).is_err());
+    }
+
+    #[test]
+    fn listings_are_revalidated_with_their_etag() {
+        let listing = r#"[{"name":"SKILL.md","path":"pdf/SKILL.md","type":"file","download_url":"https://example.com/SKILL.md"}]"#;
+        let (base, requests) = serve(vec![
+            response("200 OK", &[("ETag", "\"v1\""), ("Content-Type", "application/json")], listing),
+            response("304 Not Modified", &[("ETag", "\"v1\"")], ""),
+        ]);
+        let url = format!("{}/repos/acme/skills/contents/pdf?ref=main", base);
+        let client = http_client().unwrap();
+
+        let first = fetch_listing(&client, &url, Some("secret")).unwrap().unwrap();
+        let second = fetch_listing(&client, &url, Some("secret")).unwrap().unwrap();
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(second[0].path, "pdf/SKILL.md");
+        let requests = requests.lock().unwrap();
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+        assert!(requests.iter().all(|r| r.contains("authorization: bearer secret")));
+    }
+
+    #[test]
+    fn rate_limited_listings_say_how_to_lift_the_limit() {
+        let (base, _) = serve(vec![response("403 Forbidden", &[("x-ratelimit-remaining", "0")], "{}")]);
+        let url = format!("{}/repos/acme/skills/contents/?ref=main", base);
+        let error = fetch_listing(&http_client().unwrap(), &url, None).unwrap_err();
+        assert!(error.contains("GITHUB_TOKEN"));
+    }
+
+    fn splits(url: &str) -> Vec<(String, String)> {
+        github_candidates(url).into_iter().map(|l| (l.git_ref, l.dir)).collect()
+    }
+
+    #[test]
+    fn github_candidates_try_every_ref_split() {
+        let url = "https://raw.githubusercontent.com/acme/skills/feature/x/pdf/SKILL.md";
+        assert_eq!(splits(url), [
+            ("feature".to_string(), "x/pdf".to_string()),
+            ("feature/x".to_string(), "pdf".to_string()),
+            ("feature/x/pdf".to_string(), String::new()),
+        ]);
+    }
+
+    #[test]
+    fn github_candidates_handle_root_skills() {
+        let url = "https://raw.githubusercontent.com/acme/pdf-skill/main/SKILL.md";
+        assert_eq!(splits(url), [("main".to_string(), String::new())]);
+    }
+
+    #[test]
+    fn github_candidates_ignore_other_urls() {
+        assert!(github_candidates("https://example.com/acme/skills/main/SKILL.md").is_empty());
+        assert!(github_candidates("https://raw.githubusercontent.com/acme/skills/main/README.md").is_empty());
+        assert!(github_candidates("https://raw.githubusercontent.com/acme/skills//SKILL.md").is_empty());
+    }
+}
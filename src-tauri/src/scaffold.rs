@@ -0,0 +1,408 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::clients::{self, Client, SkillType};
+use crate::hashing;
+use crate::installer::{self, SkillFile};
+use crate::remote::SKILL_FILE;
+use crate::skill_md;
+
+// User templates live in <app data>/templates/<id>/, one folder per template. A folder
+// with the same id as a built-in template replaces it.
+const TEMPLATES_DIR: &str = "templates";
+const TEMPLATE_META_FILE: &str = "template.json";
+// Built-in files starting with a shebang are scripts, which lint expects to be executable
+const SCRIPT_MODE: u32 = 0o755;
+
+const BASIC_SKILL_MD: &str = "---
+name: {{name}}
+description: {{description}}
+---
+
+# {{title}}
+
+## When to use this skill
+
+Describe the situations where this skill applies.
+
+## Instructions
+
+1. First step
+2. Second step
+";
+
+const SCRIPTS_SKILL_MD: &str = "---
+name: {{name}}
+description: {{description}}
+---
+
+# {{title}}
+
+## Instructions
+
+Run `scripts/example.py` to do the work, then summarize its output.
+";
+
+const EXAMPLE_SCRIPT: &str = "#!/usr/bin/env python3
+\"\"\"Helper script for the {{name}} skill.\"\"\"
+
+
+def main():
+    print(\"Hello from {{name}}\")
+
+
+if __name__ == \"__main__\":
+    main()
+";
+
+const REFERENCE_SKILL_MD: &str = "---
+name: {{name}}
+description: {{description}}
+---
+
+# {{title}}
+
+## Instructions
+
+Keep this file short. Read `references/REFERENCE.md` when you need the details.
+";
+
+const REFERENCE_MD: &str = "# {{title}} reference
+
+Detailed documentation that is only loaded when needed.
+";
+
+struct BuiltinTemplate {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    files: &'static [(&'static str, &'static str)],
+}
+
+const BUILTIN_TEMPLATES: [BuiltinTemplate; 3] = [
+    BuiltinTemplate {
+        id: "basic",
+        name: "Basic",
+        description: "A single SKILL.md with instructions",
+        files: &[(SKILL_FILE, BASIC_SKILL_MD)],
+    },
+    BuiltinTemplate {
+        id: "script",
+        name: "With script",
+        description: "Instructions plus a helper script in scripts/",
+        files: &[(SKILL_FILE, SCRIPTS_SKILL_MD), ("scripts/example.py", EXAMPLE_SCRIPT)],
+    },
+    BuiltinTemplate {
+        id: "reference",
+        name: "With reference docs",
+        description: "A short SKILL.md that points to longer docs in references/",
+        files: &[(SKILL_FILE, REFERENCE_SKILL_MD), ("references/REFERENCE.md", REFERENCE_MD)],
+    },
+];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateMeta {
+    name: Option<String>,
+    description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub builtin: bool,
+    pub path: Option<String>,
+}
+
+pub fn templates_root(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(TEMPLATES_DIR))
+        .map_err(|e| format!("Could not find app data directory: {}", e))
+}
+
+fn read_meta(dir: &Path) -> TemplateMeta {
+    fs::read(dir.join(TEMPLATE_META_FILE))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn user_templates(root: &Path) -> Vec<SkillTemplate> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            if id.starts_with('.') || !path.join(SKILL_FILE).is_file() {
+                return None;
+            }
+            let meta = read_meta(&path);
+            Some(SkillTemplate {
+                name: meta.name.unwrap_or_else(|| id.clone()),
+                description: meta.description.unwrap_or_default(),
+                id,
+                builtin: false,
+                path: Some(path.to_string_lossy().to_string()),
+            })
+        })
+        .collect()
+}
+
+pub fn list_templates(root: &Path) -> Vec<SkillTemplate> {
+    let custom = user_templates(root);
+
+    let mut templates: Vec<SkillTemplate> = BUILTIN_TEMPLATES.iter()
+        .filter(|t| !custom.iter().any(|c| c.id == t.id))
+        .map(|t| SkillTemplate {
+            id: t.id.to_string(),
+            name: t.name.to_string(),
+            description: t.description.to_string(),
+            builtin: true,
+            path: None,
+        })
+        .collect();
+
+    let mut custom = custom;
+    custom.sort_by(|a, b| a.id.cmp(&b.id));
+    templates.extend(custom);
+    templates
+}
+
+fn load_template(root: &Path, id: &str) -> Result<Vec<SkillFile>, String> {
+    clients::validate_skill_name(id)?;
+
+    let dir = root.join(id);
+    if dir.join(SKILL_FILE).is_file() {
+        let mut files = Vec::new();
+        for relative in hashing::list_files(&dir)? {
+            if relative == TEMPLATE_META_FILE {
+                continue;
+            }
+            let path = dir.join(&relative);
+            let contents = fs::read(&path)
+                .map_err(|e| format!("Failed to read template file {}: {}", relative, e))?;
+            let mode = fs::metadata(&path).ok().and_then(|m| installer::file_mode(&m));
+            files.push(SkillFile { path: PathBuf::from(relative), contents, mode });
+        }
+        return Ok(files);
+    }
+
+    let builtin = BUILTIN_TEMPLATES.iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Unknown template: {}", id))?;
+    Ok(builtin.files.iter()
+        .map(|(path, contents)| SkillFile {
+            path: PathBuf::from(path),
+            contents: contents.as_bytes().to_vec(),
+            mode: contents.starts_with("#!").then_some(SCRIPT_MODE),
+        })
+        .collect())
+}
+
+// "pdf-tools" -> "Pdf Tools"
+fn title_case(name: &str) -> String {
+    name.split('-')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn replace_placeholders(text: &str, name: &str, description: &str, title: &str) -> String {
+    text.replace("{{name}}", name)
+        .replace("{{description}}", description)
+        .replace("{{title}}", title)
+}
+
+// Frontmatter values are written as YAML scalars so descriptions containing `:` or `#`
+// still parse; the rest of the file gets the plain text
+fn render_skill_md(text: &str, name: &str, description: &str, title: &str) -> Result<String, String> {
+    let yaml_scalar = |value: &str| -> Result<String, String> {
+        serde_yaml::to_string(value)
+            .map(|s| s.trim_end().to_string())
+            .map_err(|e| format!("Failed to render template: {}", e))
+    };
+
+    let frontmatter_end = text.strip_prefix("---")
+        .and_then(|rest| rest.find("\n---"))
+        .map(|offset| offset + 3 + "\n---".len());
+    let Some(end) = frontmatter_end else {
+        return Ok(replace_placeholders(text, name, description, title));
+    };
+
+    let (frontmatter, body) = text.split_at(end);
+    Ok(format!(
+        "{}{}",
+        replace_placeholders(frontmatter, &yaml_scalar(name)?, &yaml_scalar(description)?, &yaml_scalar(title)?),
+        replace_placeholders(body, name, description, title)
+    ))
+}
+
+pub fn render_template(files: Vec<SkillFile>, name: &str, description: &str) -> Result<Vec<SkillFile>, String> {
+    let title = title_case(name);
+
+    files.into_iter()
+        .map(|file| {
+            // Binary assets are copied as they are
+            let Ok(text) = String::from_utf8(file.contents.clone()) else {
+                return Ok(file);
+            };
+            let rendered = if file.path == Path::new(SKILL_FILE) {
+                render_skill_md(&text, name, description, &title)?
+            } else {
+                replace_placeholders(&text, name, description, &title)
+            };
+            Ok(SkillFile { path: file.path, contents: rendered.into_bytes(), mode: file.mode })
+        })
+        .collect()
+}
+
+fn scaffold_target(
+    name: &str,
+    client: Option<Client>,
+    skill_type: Option<SkillType>,
+    project_path: Option<&str>,
+    path: Option<&str>,
+) -> Result<PathBuf, String> {
+    match (path.filter(|p| !p.is_empty()), client) {
+        (Some(path), _) => Ok(clients::expand_home(path)?.join(name)),
+        (None, Some(client)) => {
+            let skill_type = skill_type.unwrap_or(SkillType::Personal);
+            Ok(clients::skill_root(client, skill_type, project_path)?.join(name))
+        }
+        (None, None) => Err("Choose a client or a folder for the new skill".to_string()),
+    }
+}
+
+pub fn scaffold(
+    templates: &Path,
+    name: &str,
+    description: &str,
+    template: &str,
+    target: &Path,
+    scripts: bool,
+    references: bool,
+) -> Result<(), String> {
+    if !skill_md::is_valid_skill_name(name) || name.chars().count() > skill_md::MAX_NAME_LENGTH {
+        return Err(format!(
+            "Skill names must be at most {} lowercase letters, digits and hyphens: {}",
+            skill_md::MAX_NAME_LENGTH,
+            name
+        ));
+    }
+    let description = description.trim();
+    if description.is_empty() {
+        return Err("A description is required".to_string());
+    }
+    if fs::symlink_metadata(target).is_ok() {
+        return Err(format!("{} already exists", target.display()));
+    }
+
+    let files = render_template(load_template(templates, template)?, name, description)?;
+    installer::validate_skill_files(&files)?;
+    installer::install_files(target, &files)?;
+
+    for (wanted, dir) in [(scripts, "scripts"), (references, "references")] {
+        if wanted {
+            fs::create_dir_all(target.join(dir))
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_skill_templates(app: AppHandle) -> Result<Vec<SkillTemplate>, String> {
+    Ok(list_templates(&templates_root(&app)?))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn create_skill(
+    app: AppHandle,
+    name: String,
+    description: String,
+    template: Option<String>,
+    client: Option<Client>,
+    skill_type: Option<SkillType>,
+    project_path: Option<String>,
+    path: Option<String>,
+    scripts: Option<bool>,
+    references: Option<bool>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let target = scaffold_target(&name, client, skill_type, project_path.as_deref(), path.as_deref())?;
+        scaffold(
+            &templates_root(&app)?,
+            &name,
+            &description,
+            template.as_deref().unwrap_or("basic"),
+            &target,
+            scripts.unwrap_or(false),
+            references.unwrap_or(false),
+        )?;
+        clients::path_to_string(&target)
+    })
+    .await
+    .map_err(|e| format!("Create skill failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint;
+
+    #[test]
+    fn every_builtin_template_scaffolds_a_skill_that_lints_clean() {
+        let root = std::env::temp_dir().join(format!("skiller-test-{}-scaffold", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let templates = root.join(TEMPLATES_DIR);
+
+        let mut reports = Vec::new();
+        for template in list_templates(&templates) {
+            let name = format!("{}-skill", template.id);
+            let target = root.join(&name);
+            scaffold(&templates, &name, "Does things: for tests", &template.id, &target, true, true).unwrap();
+            reports.push((template.id, lint::lint(&target).unwrap()));
+        }
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(reports.len(), BUILTIN_TEMPLATES.len());
+        for (id, report) in reports {
+            let diagnostics: Vec<String> = report.diagnostics.iter().map(|d| d.to_string()).collect();
+            assert!(diagnostics.is_empty(), "{} template: {:?}", id, diagnostics);
+        }
+    }
+
+    #[test]
+    fn scaffold_refuses_bad_names_and_existing_folders() {
+        let root = std::env::temp_dir().join(format!("skiller-test-{}-scaffold-refuse", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("taken")).unwrap();
+        let templates = root.join(TEMPLATES_DIR);
+
+        let bad_name = scaffold(&templates, "Bad Name", "x", "basic", &root.join("Bad Name"), false, false);
+        let no_description = scaffold(&templates, "fine", "  ", "basic", &root.join("fine"), false, false);
+        let taken = scaffold(&templates, "taken", "x", "basic", &root.join("taken"), false, false);
+        let _ = fs::remove_dir_all(&root);
+        assert!(bad_name.is_err());
+        assert!(no_description.is_err());
+        assert!(taken.is_err());
+    }
+}
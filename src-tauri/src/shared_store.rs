@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::clients;
+use crate::hashing;
+use crate::installer::{self, ClientInstallResult, InstallStatus, SkillFile};
+use crate::lockfile::LockSource;
+use crate::remote::SKILL_FILE;
+
+// Canonical copies live under <app data>/skills/<name>; client skill directories link to them
+const SHARED_DIR: &str = "skills";
+const LINKS_STORE: &str = "skill-links.json";
+const LINKS_KEY: &str = "links";
+// Where each shared copy came from, keyed by skill name
+const OWNERS_KEY: &str = "owners";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillLink {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkStatus {
+    Ok,
+    NeedsRepair,
+    Repaired,
+    Replaced,
+    MissingSource,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkReport {
+    pub name: String,
+    pub path: String,
+    pub status: LinkStatus,
+    pub message: Option<String>,
+}
+
+pub fn shared_root(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(SHARED_DIR))
+        .map_err(|e| format!("Could not find app data directory: {}", e))
+}
+
+pub fn install_shared(app: &AppHandle, name: &str, files: &[SkillFile]) -> Result<PathBuf, String> {
+    clients::validate_skill_name(name)?;
+    let path = shared_root(app)?.join(name);
+    installer::install_files(&path, files)?;
+    Ok(path)
+}
+
+#[cfg(unix)]
+fn create_dir_link(source: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(source, link)
+}
+
+#[cfg(windows)]
+fn create_dir_link(source: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_dir(source, link)
+}
+
+pub fn is_link_to(link: &Path, source: &Path) -> bool {
+    fs::read_link(link).map(|target| target == source).unwrap_or(false) && link.exists()
+}
+
+// Replaces whatever is at `link` with a symlink to `source`, using the same staged swap as copies
+pub fn link_skill(link: &Path, source: &Path) -> Result<(), String> {
+    installer::ensure_parent_dir(link)?;
+    let staging = installer::sibling_temp_path(link, "link");
+    create_dir_link(source, &staging)
+        .map_err(|e| format!("Failed to create link to {}: {}", source.display(), e))?;
+    installer::swap_into_place(&staging, link)
+}
+
+fn load_links(app: &AppHandle) -> Result<Vec<SkillLink>, String> {
+    let store = app.store(LINKS_STORE)
+        .map_err(|e| format!("Failed to open link store: {}", e))?;
+
+    match store.get(LINKS_KEY) {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse link store: {}", e)),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn load_owners(app: &AppHandle) -> Result<BTreeMap<String, LockSource>, String> {
+    let store = app.store(LINKS_STORE)
+        .map_err(|e| format!("Failed to open link store: {}", e))?;
+
+    match store.get(OWNERS_KEY) {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse link store: {}", e)),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+fn save_owners(app: &AppHandle, owners: &BTreeMap<String, LockSource>) -> Result<(), String> {
+    let store = app.store(LINKS_STORE)
+        .map_err(|e| format!("Failed to open link store: {}", e))?;
+    let value = serde_json::to_value(owners)
+        .map_err(|e| format!("Failed to serialize link store: {}", e))?;
+
+    store.set(OWNERS_KEY, value);
+    store.save()
+        .map_err(|e| format!("Failed to save link store: {}", e))
+}
+
+fn describe_source(source: &LockSource) -> &str {
+    match source {
+        LockSource::Url { url } => url,
+        LockSource::Path { path } => path,
+    }
+}
+
+// The shared copy is keyed by name only, so a skill with the same name from another source
+// would replace it under every client already linked to it. Refuse instead.
+pub fn check_owner(app: &AppHandle, name: &str, source: Option<&LockSource>, files: &[SkillFile]) -> Result<(), String> {
+    let shared = shared_root(app)?.join(name);
+    if !shared.join(SKILL_FILE).is_file() {
+        return Ok(());
+    }
+    let Some(owner) = load_owners(app)?.remove(name) else {
+        return Ok(());
+    };
+
+    match source {
+        Some(source) if source == &owner => Ok(()),
+        None if installer::matches_installed(&shared, files) => Ok(()),
+        Some(source) => Err(format!(
+            "The shared copy of {} comes from {}, not {}; remove its links first or install it as a copy",
+            name, describe_source(&owner), describe_source(source),
+        )),
+        None => Err(format!(
+            "The shared copy of {} comes from {}; remove its links first or install it as a copy",
+            name, describe_source(&owner),
+        )),
+    }
+}
+
+fn save_links(app: &AppHandle, links: &[SkillLink]) -> Result<(), String> {
+    let store = app.store(LINKS_STORE)
+        .map_err(|e| format!("Failed to open link store: {}", e))?;
+    let value = serde_json::to_value(links)
+        .map_err(|e| format!("Failed to serialize link store: {}", e))?;
+
+    store.set(LINKS_KEY, value);
+    store.save()
+        .map_err(|e| format!("Failed to save link store: {}", e))
+}
+
+pub fn record_links(
+    app: &AppHandle,
+    name: &str,
+    source: Option<&LockSource>,
+    results: &[ClientInstallResult],
+) -> Result<(), String> {
+    if let Some(source) = source {
+        let mut owners = load_owners(app)?;
+        owners.insert(name.to_string(), source.clone());
+        save_owners(app, &owners)?;
+    }
+
+    let mut links = load_links(app)?;
+
+    for result in results.iter().filter(|r| r.status != InstallStatus::Failed) {
+        let Some(path) = &result.path else { continue };
+        links.retain(|l| &l.path != path);
+        links.push(SkillLink {
+            name: name.to_string(),
+            path: path.clone(),
+        });
+    }
+
+    save_links(app, &links)
+}
+
+pub fn forget_link(app: &AppHandle, path: &Path) -> Result<(), String> {
+    let path = clients::path_to_string(path)?;
+    let mut links = load_links(app)?;
+    let Some(index) = links.iter().position(|l| l.path == path) else {
+        return Ok(());
+    };
+    let removed = links.remove(index);
+    save_links(app, &links)?;
+
+    // Once nothing links to the shared copy, another source may take the name over
+    if !links.iter().any(|l| l.name == removed.name) {
+        let mut owners = load_owners(app)?;
+        if owners.remove(&removed.name).is_some() {
+            save_owners(app, &owners)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_link(link: &SkillLink, source: &Path, dry_run: bool) -> (LinkStatus, Option<String>) {
+    let path = Path::new(&link.path);
+
+    if !source.join(SKILL_FILE).is_file() {
+        return (
+            LinkStatus::MissingSource,
+            Some(format!("Shared copy {} no longer exists", source.display())),
+        );
+    }
+
+    let needs_repair = match fs::symlink_metadata(path) {
+        Err(_) => Some("Link was deleted".to_string()),
+        Ok(metadata) if metadata.file_type().is_symlink() => {
+            if is_link_to(path, source) {
+                None
+            } else {
+                Some("Link is broken or points elsewhere".to_string())
+            }
+        }
+        // A client (or another installer) wrote a real copy over the link. Only relink it
+        // when the copy is identical, otherwise local edits would be lost.
+        Ok(metadata) if metadata.is_dir() => {
+            let same = match (hashing::dir_hash(path), hashing::dir_hash(source)) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => false,
+            };
+            if !same {
+                return (
+                    LinkStatus::Replaced,
+                    Some("Replaced by a directory with different contents; left in place".to_string()),
+                );
+            }
+            Some("Replaced by an identical copy".to_string())
+        }
+        Ok(_) => {
+            return (
+                LinkStatus::Replaced,
+                Some("Replaced by a file; left in place".to_string()),
+            );
+        }
+    };
+
+    match needs_repair {
+        None => (LinkStatus::Ok, None),
+        Some(reason) if dry_run => (LinkStatus::NeedsRepair, Some(reason)),
+        Some(reason) => match link_skill(path, source) {
+            Ok(()) => (LinkStatus::Repaired, Some(reason)),
+            Err(e) => (LinkStatus::Failed, Some(e)),
+        },
+    }
+}
+
+pub fn repair_links(app: &AppHandle, dry_run: bool) -> Result<Vec<LinkReport>, String> {
+    let root = shared_root(app)?;
+
+    Ok(load_links(app)?
+        .iter()
+        .map(|link| {
+            let (status, message) = check_link(link, &root.join(&link.name), dry_run);
+            LinkReport {
+                name: link.name.clone(),
+                path: link.path.clone(),
+                status,
+                message,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn repair_skill_links(app: AppHandle, dry_run: Option<bool>) -> Result<Vec<LinkReport>, String> {
+    tauri::async_runtime::spawn_blocking(move || repair_links(&app, dry_run.unwrap_or(false)))
+        .await
+        .map_err(|e| format!("Link repair failed: {}", e))?
+}
@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+use crate::remote::SKILL_FILE;
+
+pub const MAX_NAME_LENGTH: usize = 64;
+pub const MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillFrontmatter {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub allowed_tools: Vec<String>,
+    pub version: Option<String>,
+    pub license: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedSkill {
+    pub frontmatter: SkillFrontmatter,
+    pub body: String,
+    // 1-based line in SKILL.md where the body starts
+    pub body_line: usize,
+    // 1-based line of each top-level frontmatter key
+    pub field_lines: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ValidationCode {
+    MissingFrontmatter,
+    UnterminatedFrontmatter,
+    InvalidYaml,
+    MissingName,
+    InvalidName,
+    NameTooLong,
+    NameMismatch,
+    MissingDescription,
+    DescriptionTooLong,
+    InvalidField,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationError {
+    pub code: ValidationCode,
+    pub field: Option<String>,
+    pub message: String,
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillReport {
+    pub path: String,
+    pub skill: Option<ParsedSkill>,
+    pub errors: Vec<ValidationError>,
+    pub valid: bool,
+}
+
+impl ValidationCode {
+    // Naming and length problems are worth reporting but don't stop an agent from loading the skill
+    pub fn blocks_install(&self) -> bool {
+        !matches!(
+            self,
+            ValidationCode::InvalidName
+                | ValidationCode::NameTooLong
+                | ValidationCode::NameMismatch
+                | ValidationCode::DescriptionTooLong
+        )
+    }
+}
+
+impl ValidationError {
+    fn new(code: ValidationCode, field: Option<&str>, message: String, line: Option<usize>) -> Self {
+        ValidationError {
+            code,
+            field: field.map(|f| f.to_string()),
+            message,
+            line,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+fn yaml_to_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// `allowed-tools` is written either as a list or as a comma/space separated string
+fn yaml_to_tools(value: &serde_yaml::Value) -> Option<Vec<String>> {
+    match value {
+        serde_yaml::Value::Sequence(items) => items.iter().map(yaml_to_string).collect(),
+        serde_yaml::Value::String(s) => Some(
+            s.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string())
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn field_error(field: &str, expected: &str, lines: &BTreeMap<String, usize>) -> ValidationError {
+    ValidationError::new(
+        ValidationCode::InvalidField,
+        Some(field),
+        format!("`{}` must be {}", field, expected),
+        lines.get(field).copied(),
+    )
+}
+
+pub fn parse_skill_md(content: &str) -> Result<ParsedSkill, ValidationError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut lines = content.lines();
+
+    if lines.next().map(|l| l.trim_end()) != Some("---") {
+        return Err(ValidationError::new(
+            ValidationCode::MissingFrontmatter,
+            None,
+            format!("{} must start with a `---` frontmatter block", SKILL_FILE),
+            Some(1),
+        ));
+    }
+
+    let mut yaml_lines = Vec::new();
+    let mut closed = false;
+    for line in lines.by_ref() {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            closed = true;
+            break;
+        }
+        yaml_lines.push(line);
+    }
+
+    if !closed {
+        return Err(ValidationError::new(
+            ValidationCode::UnterminatedFrontmatter,
+            None,
+            "Frontmatter is missing its closing `---`".to_string(),
+            Some(1),
+        ));
+    }
+
+    let mut field_lines = BTreeMap::new();
+    for (index, line) in yaml_lines.iter().enumerate() {
+        if line.starts_with(|c: char| c.is_whitespace() || c == '#' || c == '-') {
+            continue;
+        }
+        if let Some((key, _)) = line.split_once(':') {
+            field_lines.entry(key.trim().to_string()).or_insert(index + 2);
+        }
+    }
+
+    let yaml = yaml_lines.join("\n");
+    let value: serde_yaml::Value = if yaml.trim().is_empty() {
+        serde_yaml::Value::Mapping(Default::default())
+    } else {
+        serde_yaml::from_str(&yaml).map_err(|e| {
+            ValidationError::new(
+                ValidationCode::InvalidYaml,
+                None,
+                format!("Invalid frontmatter YAML: {}", e),
+                e.location().map(|l| l.line() + 1),
+            )
+        })?
+    };
+
+    let serde_yaml::Value::Mapping(mapping) = value else {
+        return Err(ValidationError::new(
+            ValidationCode::InvalidYaml,
+            None,
+            "Frontmatter must be a mapping of `key: value` pairs".to_string(),
+            Some(2),
+        ));
+    };
+
+    let mut frontmatter = SkillFrontmatter::default();
+    for (key, value) in &mapping {
+        let Some(key) = key.as_str() else { continue };
+        match key {
+            "name" => {
+                frontmatter.name = Some(yaml_to_string(value)
+                    .ok_or_else(|| field_error(key, "a string", &field_lines))?);
+            }
+            "description" => {
+                frontmatter.description = Some(yaml_to_string(value)
+                    .ok_or_else(|| field_error(key, "a string", &field_lines))?);
+            }
+            "allowed-tools" => {
+                frontmatter.allowed_tools = yaml_to_tools(value)
+                    .ok_or_else(|| field_error(key, "a list of tool names", &field_lines))?;
+            }
+            "version" => {
+                frontmatter.version = Some(yaml_to_string(value)
+                    .ok_or_else(|| field_error(key, "a string", &field_lines))?);
+            }
+            "license" => {
+                frontmatter.license = Some(yaml_to_string(value)
+                    .ok_or_else(|| field_error(key, "a string", &field_lines))?);
+            }
+            "metadata" => {
+                frontmatter.metadata = serde_json::to_value(value).ok();
+            }
+            _ => {
+                if let Ok(json) = serde_json::to_value(value) {
+                    frontmatter.extra.insert(key.to_string(), json);
+                }
+            }
+        }
+    }
+
+    let body_line = yaml_lines.len() + 3;
+    let body = content.lines().skip(body_line - 1).collect::<Vec<_>>().join("\n");
+
+    Ok(ParsedSkill {
+        frontmatter,
+        body,
+        body_line,
+        field_lines,
+    })
+}
+
+pub fn is_valid_skill_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+pub fn validate_skill(skill: &ParsedSkill, dir_name: Option<&str>) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let frontmatter = &skill.frontmatter;
+    let line = |field: &str| skill.field_lines.get(field).copied();
+
+    match frontmatter.name.as_deref().map(str::trim) {
+        None | Some("") => errors.push(ValidationError::new(
+            ValidationCode::MissingName,
+            Some("name"),
+            "Frontmatter is missing `name`".to_string(),
+            line("name").or(Some(1)),
+        )),
+        Some(name) => {
+            if name.chars().count() > MAX_NAME_LENGTH {
+                errors.push(ValidationError::new(
+                    ValidationCode::NameTooLong,
+                    Some("name"),
+                    format!("`name` is longer than {} characters", MAX_NAME_LENGTH),
+                    line("name"),
+                ));
+            }
+            if !is_valid_skill_name(name) {
+                errors.push(ValidationError::new(
+                    ValidationCode::InvalidName,
+                    Some("name"),
+                    format!("`name` must use lowercase letters, digits and hyphens: {}", name),
+                    line("name"),
+                ));
+            }
+            if let Some(dir_name) = dir_name {
+                if dir_name != name {
+                    errors.push(ValidationError::new(
+                        ValidationCode::NameMismatch,
+                        Some("name"),
+                        format!("`name` is `{}` but the skill directory is `{}`", name, dir_name),
+                        line("name"),
+                    ));
+                }
+            }
+        }
+    }
+
+    match frontmatter.description.as_deref().map(str::trim) {
+        None | Some("") => errors.push(ValidationError::new(
+            ValidationCode::MissingDescription,
+            Some("description"),
+            "Frontmatter is missing `description`".to_string(),
+            line("description").or(Some(1)),
+        )),
+        Some(description) => {
+            let length = description.chars().count();
+            if length > MAX_DESCRIPTION_LENGTH {
+                errors.push(ValidationError::new(
+                    ValidationCode::DescriptionTooLong,
+                    Some("description"),
+                    format!("`description` is {} characters, the limit is {}", length, MAX_DESCRIPTION_LENGTH),
+                    line("description"),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+// Accepts either a skill directory or the SKILL.md inside it
+pub fn resolve_skill_file(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.join(SKILL_FILE)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+pub fn check_skill(content: &str, dir_name: Option<&str>) -> (Option<ParsedSkill>, Vec<ValidationError>) {
+    match parse_skill_md(content) {
+        Ok(skill) => {
+            let errors = validate_skill(&skill, dir_name);
+            (Some(skill), errors)
+        }
+        Err(error) => (None, vec![error]),
+    }
+}
+
+// Used before installing: the directory name is chosen by the installer, so it isn't compared
+pub fn ensure_installable(content: &[u8]) -> Result<ParsedSkill, String> {
+    let content = std::str::from_utf8(content)
+        .map_err(|_| format!("{} is not valid UTF-8", SKILL_FILE))?;
+
+    let (skill, errors) = check_skill(content, None);
+    let errors: Vec<ValidationError> = errors.into_iter().filter(|e| e.code.blocks_install()).collect();
+
+    match skill {
+        Some(skill) if errors.is_empty() => Ok(skill),
+        _ => Err(format!(
+            "Invalid {}: {}",
+            SKILL_FILE,
+            errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")
+        )),
+    }
+}
+
+pub fn read_skill_report(path: &Path) -> Result<SkillReport, String> {
+    let file = resolve_skill_file(path);
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let dir_name = file.parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string());
+
+    let (skill, errors) = check_skill(&content, dir_name.as_deref());
+    Ok(SkillReport {
+        path: file.to_string_lossy().to_string(),
+        valid: skill.is_some() && errors.is_empty(),
+        skill,
+        errors,
+    })
+}
+
+#[tauri::command]
+pub async fn parse_skill(path: String) -> Result<SkillReport, String> {
+    read_skill_report(Path::new(&path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(errors: &[ValidationError]) -> Vec<ValidationCode> {
+        errors.iter().map(|e| e.code).collect()
+    }
+
+    fn parse_error(content: &str) -> ValidationCode {
+        parse_skill_md(content).unwrap_err().code
+    }
+
+    fn check(name: &str, description: &str, dir_name: Option<&str>) -> Vec<ValidationCode> {
+        let content = format!("---\nname: {}\ndescription: {}\n---\n", name, description);
+        codes(&check_skill(&content, dir_name).1)
+    }
+
+    #[test]
+    fn frontmatter_must_open_and_close() {
+        assert_eq!(parse_error(""), ValidationCode::MissingFrontmatter);
+        assert_eq!(parse_error("# PDF\n\nname: pdf\n"), ValidationCode::MissingFrontmatter);
+        assert_eq!(parse_error("---\nname: pdf\ndescription: d\n"), ValidationCode::UnterminatedFrontmatter);
+        assert_eq!(parse_error("---\nname: [pdf\n---\n"), ValidationCode::InvalidYaml);
+        assert_eq!(parse_error("---\n- pdf\n---\n"), ValidationCode::InvalidYaml);
+    }
+
+    #[test]
+    fn crlf_and_byte_order_marks_are_accepted() {
+        let skill = parse_skill_md("\u{feff}---\r\nname: pdf\r\ndescription: Fill forms\r\n---\r\nBody line\r\nSecond\r\n").unwrap();
+        assert_eq!(skill.frontmatter.name.as_deref(), Some("pdf"));
+        assert_eq!(skill.frontmatter.description.as_deref(), Some("Fill forms"));
+        assert_eq!(skill.body, "Body line\nSecond");
+        assert_eq!(skill.body_line, 5);
+        assert_eq!(skill.field_lines.get("description"), Some(&3));
+    }
+
+    #[test]
+    fn quoted_and_multiline_values_are_read_as_yaml() {
+        let content = "---
+name: 'pdf'
+description: >
+  Fill PDF forms:
+  one field at a time
+version: 1.2
+license: \"MIT # not a comment\"
+---
+";
+        let frontmatter = parse_skill_md(content).unwrap().frontmatter;
+        assert_eq!(frontmatter.name.as_deref(), Some("pdf"));
+        assert_eq!(frontmatter.description.as_deref(), Some("Fill PDF forms: one field at a time\n"));
+        assert_eq!(frontmatter.version.as_deref(), Some("1.2"));
+        assert_eq!(frontmatter.license.as_deref(), Some("MIT # not a comment"));
+    }
+
+    #[test]
+    fn allowed_tools_can_be_a_list_or_a_string() {
+        let list = parse_skill_md("---\nallowed-tools:\n  - Read\n  - Bash(git:*)\n---\n").unwrap();
+        assert_eq!(list.frontmatter.allowed_tools, ["Read", "Bash(git:*)"]);
+
+        let string = parse_skill_md("---\nallowed-tools: Read, Grep Glob\n---\n").unwrap();
+        assert_eq!(string.frontmatter.allowed_tools, ["Read", "Grep", "Glob"]);
+
+        let error = parse_skill_md("---\nname: pdf\nallowed-tools:\n  read: true\n---\n").unwrap_err();
+        assert_eq!(error.code, ValidationCode::InvalidField);
+        assert_eq!(error.field.as_deref(), Some("allowed-tools"));
+        assert_eq!(error.line, Some(3));
+    }
+
+    #[test]
+    fn unknown_keys_and_metadata_are_kept() {
+        let frontmatter = parse_skill_md("---\nname: pdf\nmetadata:\n  owner: docs\nmodel: opus\n---\n").unwrap().frontmatter;
+        assert_eq!(frontmatter.metadata, Some(serde_json::json!({"owner": "docs"})));
+        assert_eq!(frontmatter.extra.get("model"), Some(&serde_json::json!("opus")));
+    }
+
+    #[test]
+    fn valid_skills_have_no_errors() {
+        assert!(check("pdf-tools-2", "Fill forms", Some("pdf-tools-2")).is_empty());
+        assert!(check(&"a".repeat(MAX_NAME_LENGTH), &"d".repeat(MAX_DESCRIPTION_LENGTH), None).is_empty());
+    }
+
+    #[test]
+    fn names_are_checked_for_length_characters_and_folder() {
+        assert_eq!(check(&"a".repeat(MAX_NAME_LENGTH + 1), "d", None), [ValidationCode::NameTooLong]);
+        for name in ["PDF", "pdf_tools", "-pdf", "pdf-", "pdf tools", "pdé"] {
+            assert_eq!(check(&format!("\"{}\"", name), "d", None), [ValidationCode::InvalidName], "{}", name);
+        }
+        assert_eq!(check("pdf", "d", Some("pdf-tools")), [ValidationCode::NameMismatch]);
+    }
+
+    #[test]
+    fn missing_and_overlong_fields_are_reported() {
+        let errors = check_skill("---\nversion: 1\n---\n", None).1;
+        assert_eq!(codes(&errors), [ValidationCode::MissingName, ValidationCode::MissingDescription]);
+        assert_eq!(errors[0].line, Some(1));
+
+        assert_eq!(check("pdf", "\"  \"", None), [ValidationCode::MissingDescription]);
+        assert_eq!(check("pdf", &"d".repeat(MAX_DESCRIPTION_LENGTH + 1), None), [ValidationCode::DescriptionTooLong]);
+    }
+
+    #[test]
+    fn only_blocking_problems_stop_an_install() {
+        assert!(ensure_installable(b"---\nname: PDF Tools\ndescription: d\n---\n").is_ok());
+        assert!(ensure_installable(b"---\nname: pdf\n---\n").is_err());
+        assert!(ensure_installable(b"no frontmatter").is_err());
+        assert!(ensure_installable(&[0xff, 0xfe]).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::baseline::{self, Baseline};
+use crate::clients;
+use crate::hashing;
+use crate::remote::{self, Conditional, RemoteFile, SKILL_FILE};
+
+// Remembers validators and content hashes per remote URL so repeated checks
+// only transfer files that actually changed
+const UPDATE_CACHE_STORE: &str = "skill-updates.json";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheckTarget {
+    pub id: String,
+    pub path: String,
+    pub raw_file_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateStatus {
+    UpToDate,
+    UpdateAvailable,
+    NotInstalled,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillUpdateStatus {
+    pub id: String,
+    pub path: String,
+    pub status: UpdateStatus,
+    pub changed_files: Vec<String>,
+    pub local_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedRemote {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    sha256: String,
+}
+
+pub struct RemoteHashCache {
+    store: std::sync::Arc<tauri_plugin_store::Store<tauri::Wry>>,
+}
+
+impl RemoteHashCache {
+    pub fn open(app: &AppHandle) -> Result<Self, String> {
+        let store = app.store(UPDATE_CACHE_STORE)
+            .map_err(|e| format!("Failed to open update cache: {}", e))?;
+        Ok(RemoteHashCache { store })
+    }
+
+    fn get(&self, url: &str) -> Option<CachedRemote> {
+        self.store.get(url).and_then(|v| serde_json::from_value(v).ok())
+    }
+
+    fn set(&self, url: &str, entry: &CachedRemote) {
+        if let Ok(value) = serde_json::to_value(entry) {
+            self.store.set(url, value);
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.store.save()
+            .map_err(|e| format!("Failed to save update cache: {}", e))
+    }
+
+    // Hash of the remote file, or None if it doesn't exist upstream
+    pub fn remote_hash(&self, url: &str) -> Result<Option<String>, String> {
+        let cached = self.get(url);
+        let (etag, last_modified) = match &cached {
+            Some(c) => (c.etag.as_deref(), c.last_modified.as_deref()),
+            None => (None, None),
+        };
+
+        match remote::fetch_conditional(url, etag, last_modified)? {
+            Conditional::NotModified => match cached {
+                Some(c) => Ok(Some(c.sha256)),
+                None => Err(format!("Server returned 304 for {} without a cached copy", url)),
+            },
+            Conditional::NotFound => Ok(None),
+            Conditional::Modified { bytes, etag, last_modified } => {
+                let sha256 = hashing::sha256_hex(&bytes);
+                self.set(url, &CachedRemote { etag, last_modified, sha256: sha256.clone() });
+                Ok(Some(sha256))
+            }
+        }
+    }
+}
+
+// Compares upstream against the files as Skiller installed them (the baseline), so local
+// edits don't read as updates; without a baseline the local files stand in for it. Listed
+// upstream files catch additions, and known files are also probed by relative URL since
+// listing only works for GitHub. SKILL.md must exist remotely.
+// Upstream URL of every file to compare: the listed ones plus the reference files, which are
+// probed by relative URL since listing only works for GitHub. Hidden files are left out on
+// both sides because local hashes never include them.
+fn upstream_urls(
+    raw_file_url: &str,
+    listed: Vec<RemoteFile>,
+    reference: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut urls: BTreeMap<String, String> = listed.into_iter()
+        .filter(|f| hashing::is_tracked(&f.path))
+        .map(|f| (f.path, f.url))
+        .collect();
+    for relative in reference.keys() {
+        if !urls.contains_key(relative) {
+            urls.insert(relative.clone(), remote::sibling_url(raw_file_url, relative)?);
+        }
+    }
+    urls.insert(SKILL_FILE.to_string(), raw_file_url.to_string());
+    Ok(urls)
+}
+
+fn check_target(cache: &RemoteHashCache, target: &UpdateCheckTarget, baseline: Option<&Baseline>) -> Result<SkillUpdateStatus, String> {
+    let dir = clients::expand_home(&target.path)?;
+    if !dir.join(SKILL_FILE).is_file() {
+        return Ok(SkillUpdateStatus {
+            id: target.id.clone(),
+            path: target.path.clone(),
+            status: UpdateStatus::NotInstalled,
+            changed_files: Vec::new(),
+            local_hash: None,
+            error: None,
+        });
+    }
+
+    let local = hashing::file_hashes(&dir)?;
+    let reference = baseline.map(|b| &b.files).unwrap_or(&local);
+
+    let listed = remote::list_skill_files(&target.raw_file_url)?;
+    let urls = upstream_urls(&target.raw_file_url, listed, reference)?;
+
+    let mut changed_files = Vec::new();
+    for (relative, url) in &urls {
+        let known = reference.get(relative);
+        match (cache.remote_hash(url)?, known) {
+            (None, _) if relative == SKILL_FILE => {
+                return Err(format!("{} no longer exists upstream", SKILL_FILE));
+            }
+            (Some(remote_hash), Some(known)) if &remote_hash == known => {}
+            // Without a baseline, a file missing upstream is taken to be a local addition
+            (None, Some(_)) if baseline.is_none() => {}
+            (None, None) => {}
+            _ => changed_files.push(relative.clone()),
+        }
+    }
+
+    Ok(SkillUpdateStatus {
+        id: target.id.clone(),
+        path: target.path.clone(),
+        status: if changed_files.is_empty() { UpdateStatus::UpToDate } else { UpdateStatus::UpdateAvailable },
+        changed_files,
+        local_hash: Some(hashing::combined_hash(&local)),
+        error: None,
+    })
+}
+
+pub fn check_updates(app: &AppHandle, cache: &RemoteHashCache, targets: &[UpdateCheckTarget]) -> Vec<SkillUpdateStatus> {
+    targets.iter()
+        .map(|target| {
+            let baseline = clients::expand_home(&target.path)
+                .and_then(|dir| baseline::load_baseline(app, &dir))
+                .unwrap_or_default();
+            check_target(cache, target, baseline.as_ref()).unwrap_or_else(|e| SkillUpdateStatus {
+                id: target.id.clone(),
+                path: target.path.clone(),
+                status: UpdateStatus::Error,
+                changed_files: Vec::new(),
+                local_hash: clients::expand_home(&target.path)
+                    .ok()
+                    .and_then(|dir| hashing::dir_hash(&dir).ok()),
+                error: Some(e),
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn check_skill_updates(
+    app: AppHandle,
+    skills: Vec<UpdateCheckTarget>,
+) -> Result<Vec<SkillUpdateStatus>, String> {
+    let cache = RemoteHashCache::open(&app)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let results = check_updates(&app, &cache, &skills);
+        cache.save()?;
+        Ok(results)
+    })
+    .await
+    .map_err(|e| format!("Update check failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW: &str = "https://raw.githubusercontent.com/acme/skills/main/pdf/SKILL.md";
+
+    fn listed(path: &str) -> RemoteFile {
+        RemoteFile { path: path.to_string(), url: format!("https://example.com/{}", path) }
+    }
+
+    #[test]
+    fn upstream_urls_leave_out_hidden_files() {
+        let listing = vec![
+            listed(SKILL_FILE),
+            listed(".env.example"),
+            listed(".github/workflows/ci.yml"),
+            listed("scripts/.cache/x"),
+            listed("scripts/run.sh"),
+        ];
+        let reference = BTreeMap::from([(SKILL_FILE.to_string(), "hash".to_string())]);
+        let urls = upstream_urls(RAW, listing, &reference).unwrap();
+
+        assert_eq!(urls.keys().collect::<Vec<_>>(), [SKILL_FILE, "scripts/run.sh"]);
+        assert_eq!(urls[SKILL_FILE], RAW);
+    }
+
+    #[test]
+    fn upstream_urls_probe_reference_files_that_are_not_listed() {
+        let reference = BTreeMap::from([
+            (SKILL_FILE.to_string(), "a".to_string()),
+            ("references/api.md".to_string(), "b".to_string()),
+        ]);
+        let urls = upstream_urls(RAW, vec![listed(SKILL_FILE)], &reference).unwrap();
+
+        assert_eq!(
+            urls["references/api.md"],
+            "https://raw.githubusercontent.com/acme/skills/main/pdf/references/api.md",
+        );
+    }
+
+    #[test]
+    fn local_hashes_and_the_upstream_filter_agree_on_dotfiles() {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-update-dotfiles", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join(".github")).unwrap();
+        std::fs::write(dir.join(SKILL_FILE), "skill").unwrap();
+        std::fs::write(dir.join(".env.example"), "A=1").unwrap();
+        std::fs::write(dir.join(".github/ci.yml"), "on: push").unwrap();
+
+        let local = hashing::file_hashes(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        let listing = vec![listed(SKILL_FILE), listed(".env.example"), listed(".github/ci.yml")];
+        let urls = upstream_urls(RAW, listing, &local).unwrap();
+        assert_eq!(urls.keys().collect::<Vec<_>>(), local.keys().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::clients::{Client, SkillType};
+use crate::hashing;
+use crate::inventory::{self, DiscoveredSkill, SkillRoot};
+
+// Editors and `git pull` touch many files at once; changes are collected for this long
+// before the skill roots are rescanned
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub const SKILL_ADDED: &str = "skill-added";
+pub const SKILL_CHANGED: &str = "skill-changed";
+pub const SKILL_REMOVED: &str = "skill-removed";
+// Emitted with the error when the watcher can't be started at launch
+pub const WATCH_FAILED: &str = "watch-failed";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillEvent {
+    pub name: String,
+    pub path: String,
+    pub clients: Vec<Client>,
+    pub skill_type: SkillType,
+    pub project_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchStatus {
+    // Roots that don't exist yet are watched through their nearest existing parent and
+    // reported as watching once they are created
+    pub watching: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+// Last seen content hash of every skill, keyed by its directory
+type Snapshot = BTreeMap<PathBuf, (String, DiscoveredSkill)>;
+
+impl SkillEvent {
+    fn new(skill: &DiscoveredSkill) -> Self {
+        SkillEvent {
+            name: skill.name.clone(),
+            path: skill.path.to_string_lossy().to_string(),
+            clients: skill.root.clients.clone(),
+            skill_type: skill.root.skill_type,
+            project_path: skill.root.project_path.clone(),
+        }
+    }
+}
+
+fn scan_root(root: &SkillRoot, snapshot: &mut Snapshot) {
+    for skill in inventory::discover_in_root(root) {
+        let hash = hashing::dir_hash(&skill.path).unwrap_or_default();
+        snapshot.insert(skill.path.clone(), (hash, skill));
+    }
+}
+
+// Resolves symlinks such as macOS's /var -> /private/var, which event paths already have
+// resolved, so they compare equal to root paths. A path that no longer exists is resolved
+// through its nearest existing ancestor.
+fn normalize(path: &Path) -> PathBuf {
+    for ancestor in path.ancestors() {
+        if let Ok(resolved) = fs::canonicalize(ancestor) {
+            return match path.strip_prefix(ancestor) {
+                Ok(rest) => resolved.join(rest),
+                Err(_) => resolved,
+            };
+        }
+    }
+    path.to_path_buf()
+}
+
+fn touched_roots<'a>(roots: &'a [SkillRoot], paths: &[PathBuf]) -> Vec<&'a SkillRoot> {
+    let paths: Vec<PathBuf> = paths.iter().map(|p| normalize(p)).collect();
+    roots.iter()
+        .filter(|root| {
+            let root_path = normalize(&root.path);
+            paths.iter().any(|p| p.starts_with(&root_path))
+        })
+        .collect()
+}
+
+// Rescans one root and returns an event for every skill in it that appeared, disappeared
+// or changed since the last scan
+fn rescan_root(root: &SkillRoot, snapshot: &mut Snapshot) -> Vec<(&'static str, SkillEvent)> {
+    let mut events = Vec::new();
+    let mut current = Snapshot::new();
+    scan_root(root, &mut current);
+
+    let previous: Vec<PathBuf> = snapshot.keys()
+        .filter(|path| path.parent() == Some(root.path.as_path()))
+        .cloned()
+        .collect();
+    for path in previous {
+        if !current.contains_key(&path) {
+            if let Some((_, skill)) = snapshot.remove(&path) {
+                events.push((SKILL_REMOVED, SkillEvent::new(&skill)));
+            }
+        }
+    }
+
+    for (path, (hash, skill)) in current {
+        let event = match snapshot.get(&path) {
+            None => Some(SKILL_ADDED),
+            Some((old_hash, _)) if old_hash != &hash => Some(SKILL_CHANGED),
+            Some(_) => None,
+        };
+        if let Some(event) = event {
+            events.push((event, SkillEvent::new(&skill)));
+        }
+        snapshot.insert(path, (hash, skill));
+    }
+    events
+}
+
+// Rescans the roots touched by `paths` and emits their events
+fn rescan(app: &AppHandle, roots: &[SkillRoot], snapshot: &mut Snapshot, paths: &[PathBuf]) {
+    for root in touched_roots(roots, paths) {
+        for (event, payload) in rescan_root(root, snapshot) {
+            let _ = app.emit(event, payload);
+        }
+    }
+}
+
+// The one running watcher. Roots are only ever added to it, so the personal roots watched
+// from launch and the projects the frontend adds later can arrive in any order.
+struct WatchState {
+    debouncer: Debouncer<RecommendedWatcher>,
+    project_paths: Vec<String>,
+    roots: Vec<SkillRoot>,
+    snapshot: Snapshot,
+    // Roots watched recursively
+    watched: Vec<PathBuf>,
+    // Nearest existing parents of roots that don't exist yet, watched on their own so the
+    // root is picked up once it is created
+    parents: Vec<PathBuf>,
+}
+
+static WATCHER: Mutex<Option<WatchState>> = Mutex::new(None);
+
+impl WatchState {
+    // Watches every root that exists and a parent of every one that doesn't; returns the
+    // roots that started being watched, which the caller scans in full
+    fn watch_roots(&mut self) -> Result<Vec<PathBuf>, String> {
+        // A deleted root falls back to being watched through its parent
+        let gone: Vec<PathBuf> = self.watched.iter().filter(|p| !p.is_dir()).cloned().collect();
+        for path in gone {
+            let _ = self.debouncer.watcher().unwatch(&path);
+            self.watched.retain(|p| p != &path);
+        }
+
+        let mut added = Vec::new();
+        let mut needed_parents = Vec::new();
+        for root in &self.roots {
+            if self.watched.contains(&root.path) {
+                continue;
+            }
+            if root.path.is_dir() {
+                self.debouncer.watcher()
+                    .watch(&root.path, RecursiveMode::Recursive)
+                    .map_err(|e| format!("Failed to watch {}: {}", root.path.display(), e))?;
+                self.watched.push(root.path.clone());
+                added.push(root.path.clone());
+                continue;
+            }
+            if let Some(parent) = root.path.ancestors().skip(1).find(|p| p.is_dir()) {
+                if !needed_parents.iter().any(|p: &PathBuf| p == parent) {
+                    needed_parents.push(parent.to_path_buf());
+                }
+            }
+        }
+
+        for parent in &needed_parents {
+            if !self.parents.contains(parent) {
+                self.debouncer.watcher()
+                    .watch(parent, RecursiveMode::NonRecursive)
+                    .map_err(|e| format!("Failed to watch {}: {}", parent.display(), e))?;
+            }
+        }
+        for parent in &self.parents {
+            if !needed_parents.contains(parent) {
+                let _ = self.debouncer.watcher().unwatch(parent);
+            }
+        }
+        self.parents = needed_parents;
+        Ok(added)
+    }
+
+    fn status(&self) -> WatchStatus {
+        let mut status = WatchStatus { watching: Vec::new(), missing: Vec::new() };
+        for root in &self.roots {
+            let path = root.path.to_string_lossy().to_string();
+            if self.watched.contains(&root.path) {
+                status.watching.push(path);
+            } else {
+                status.missing.push(path);
+            }
+        }
+        status
+    }
+}
+
+fn on_events(app: &AppHandle, mut paths: Vec<PathBuf>) {
+    let Ok(mut guard) = WATCHER.lock() else {
+        return;
+    };
+    let Some(state) = guard.as_mut() else {
+        return;
+    };
+    // Roots created since the last event are watched from now on and scanned in full
+    if let Ok(created) = state.watch_roots() {
+        paths.extend(created);
+    }
+    rescan(app, &state.roots, &mut state.snapshot, &paths);
+}
+
+// Starts the watcher on first use and adds the personal roots and the given projects to it.
+// Projects added earlier stay watched.
+pub fn start(app: AppHandle, project_paths: &[String]) -> Result<WatchStatus, String> {
+    let mut guard = WATCHER.lock().map_err(|e| e.to_string())?;
+    let state = match guard.as_mut() {
+        Some(state) => state,
+        None => {
+            let debouncer = new_debouncer(DEBOUNCE, move |result: DebounceEventResult| {
+                if let Ok(events) = result {
+                    on_events(&app, events.into_iter().map(|e| e.path).collect());
+                }
+            })
+            .map_err(|e| format!("Failed to start watcher: {}", e))?;
+            guard.insert(WatchState {
+                debouncer,
+                project_paths: Vec::new(),
+                roots: Vec::new(),
+                snapshot: Snapshot::new(),
+                watched: Vec::new(),
+                parents: Vec::new(),
+            })
+        }
+    };
+
+    for path in project_paths {
+        if !state.project_paths.contains(path) {
+            state.project_paths.push(path.clone());
+        }
+    }
+    let roots = inventory::skill_roots(&state.project_paths);
+    for root in roots.iter().filter(|root| !state.roots.iter().any(|r| r.path == root.path)) {
+        scan_root(root, &mut state.snapshot);
+    }
+    state.roots = roots;
+
+    // Known roots that were created since the last event start out with what they hold now
+    for path in state.watch_roots()? {
+        if let Some(root) = state.roots.iter().find(|r| r.path == path) {
+            scan_root(root, &mut state.snapshot);
+        }
+    }
+    Ok(state.status())
+}
+
+// Nobody is waiting on the launch-time start, so a failure is logged and emitted instead
+pub fn start_at_launch(app: AppHandle) {
+    if let Err(e) = start(app.clone(), &[]) {
+        eprintln!("Failed to watch skill roots: {}", e);
+        let _ = app.emit(WATCH_FAILED, e);
+    }
+}
+
+pub fn stop() -> Result<(), String> {
+    // Dropped after the lock is released, in case an event is waiting for it
+    let state = WATCHER.lock().map_err(|e| e.to_string())?.take();
+    drop(state);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn watch_skill_roots(app: AppHandle, project_paths: Option<Vec<String>>) -> Result<WatchStatus, String> {
+    let project_paths = project_paths.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || start(app, &project_paths))
+        .await
+        .map_err(|e| format!("Watcher failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn unwatch_skill_roots() -> Result<(), String> {
+    // Dropping the watcher joins its event thread, so it is done off the main thread
+    tauri::async_runtime::spawn_blocking(stop)
+        .await
+        .map_err(|e| format!("Watcher failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(path: &Path) -> SkillRoot {
+        SkillRoot {
+            path: path.to_path_buf(),
+            clients: vec![Client::ClaudeCode],
+            skill_type: SkillType::Personal,
+            project_path: None,
+        }
+    }
+
+    fn write_skill(root: &Path, name: &str, body: &str) {
+        fs::create_dir_all(root.join(name)).unwrap();
+        fs::write(root.join(name).join(crate::remote::SKILL_FILE), body).unwrap();
+    }
+
+    fn names(events: &[(&'static str, SkillEvent)]) -> Vec<(&'static str, String)> {
+        events.iter().map(|(event, skill)| (*event, skill.name.clone())).collect()
+    }
+
+    #[test]
+    fn rescan_reports_added_changed_and_removed_skills() {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-watch-rescan", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        write_skill(&dir, "pdf", "one");
+        write_skill(&dir, "docx", "one");
+        let root = root(&dir);
+        let mut snapshot = Snapshot::new();
+        scan_root(&root, &mut snapshot);
+
+        let unchanged = rescan_root(&root, &mut snapshot);
+        write_skill(&dir, "pdf", "two");
+        write_skill(&dir, "xlsx", "one");
+        fs::remove_dir_all(dir.join("docx")).unwrap();
+        let changed = rescan_root(&root, &mut snapshot);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(unchanged.is_empty());
+        assert_eq!(names(&changed), [
+            (SKILL_REMOVED, "docx".to_string()),
+            (SKILL_CHANGED, "pdf".to_string()),
+            (SKILL_ADDED, "xlsx".to_string()),
+        ]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn event_paths_match_roots_reached_through_a_symlink() {
+        let dir = std::env::temp_dir().join(format!("skiller-test-{}-watch-symlink", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("private/skills")).unwrap();
+        std::os::unix::fs::symlink(dir.join("private"), dir.join("var")).unwrap();
+        let resolved = fs::canonicalize(dir.join("private/skills")).unwrap();
+
+        // Like macOS, the root is configured through /var while events arrive under /private/var
+        let roots = [root(&dir.join("var/skills")), root(&dir.join("other"))];
+        let existing = touched_roots(&roots, &[resolved.join("pdf/SKILL.md")]).len();
+        let deleted = touched_roots(&roots, &[resolved.join("gone/SKILL.md")]).len();
+        let unrelated = touched_roots(&roots, &[dir.join("private/elsewhere")]).len();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(existing, 1);
+        assert_eq!(deleted, 1);
+        assert_eq!(unrelated, 0);
+    }
+}